@@ -0,0 +1,39 @@
+//! Serialization must work without the standard library, using only `alloc`
+#![no_std]
+
+extern crate alloc;
+// Only needed by the test harness
+extern crate std;
+
+use alloc::string::String;
+use petitset::{PetitMap, PetitSet};
+use serde_json::{from_str, to_string};
+
+#[test]
+fn no_std_serde_set() {
+    let mut set: PetitSet<u32, 4> = PetitSet::new();
+    set.insert(5);
+    set.insert(4);
+    set.insert(1);
+
+    set.remove(&4);
+
+    let intermediate_repr: String = to_string(&set).unwrap();
+    assert_eq!(intermediate_repr, "[5,null,1,null]");
+
+    let deserialized_set: PetitSet<u32, 4> = from_str(&intermediate_repr).unwrap();
+
+    assert!(set.identical(deserialized_set));
+}
+
+#[test]
+fn no_std_serde_map() {
+    let mut map: PetitMap<u32, bool, 3> = PetitMap::new();
+    map.insert(1, true);
+    map.insert(2, false);
+
+    let intermediate_repr: String = to_string(&map).unwrap();
+    let deserialized_map: PetitMap<u32, bool, 3> = from_str(&intermediate_repr).unwrap();
+
+    assert!(map.identical(deserialized_map));
+}
@@ -1,5 +1,9 @@
-use petitset::{PetitMap, PetitSet};
-use ron::{de::from_str, ser::to_string};
+use petitset::{AsMap, Compact, PetitMap, PetitSet};
+use ron::{
+    de::{from_str, Deserializer},
+    ser::to_string,
+};
+use serde::{Deserialize, Serialize};
 
 #[test]
 fn serde_map() {
@@ -115,3 +119,166 @@ fn short_serde() {
 
     assert_eq!(set, deserialized_set);
 }
+
+#[test]
+fn compact_serde_set() {
+    let mut set: PetitSet<u32, 5> = PetitSet::new();
+    set.insert(5);
+    set.insert(4);
+    set.insert(1);
+
+    set.remove(&4);
+
+    let intermediate_repr = to_string(&Compact(set)).unwrap();
+    assert_eq!(intermediate_repr, "[5,1]");
+
+    let Compact(deserialized_set): Compact<PetitSet<u32, 5>> =
+        from_str(&intermediate_repr).unwrap();
+
+    assert_eq!(set, deserialized_set);
+    // Gaps are not preserved
+    assert_eq!(deserialized_set.get_at(1), Some(&1));
+}
+
+#[test]
+fn compact_serde_map() {
+    let mut map: PetitMap<u32, &str, 5> = PetitMap::new();
+    map.insert(1, "one");
+    map.insert(5, "five");
+    map.insert(3, "three");
+
+    map.remove(&5);
+
+    let intermediate_repr = to_string(&Compact(map)).unwrap();
+    assert_eq!(intermediate_repr, "[(1,\"one\"),(3,\"three\")]");
+
+    let Compact(deserialized_map): Compact<PetitMap<u32, &str, 5>> =
+        from_str(&intermediate_repr).unwrap();
+
+    assert_eq!(map, deserialized_map);
+}
+
+#[test]
+fn compact_serde_overflow() {
+    let result: Result<Compact<PetitSet<u32, 2>>, _> = from_str("[1,2,3,4,5,6,7,8]");
+    let message = result.unwrap_err().to_string();
+    assert!(
+        message.contains("more than 2 distinct elements for a PetitSet of capacity 2"),
+        "{message}"
+    );
+
+    let result: Result<Compact<PetitMap<u32, u32, 1>>, _> = from_str("[(1,1),(2,2),(3,3)]");
+    let message = result.unwrap_err().to_string();
+    assert!(
+        message.contains("more than 1 distinct keys for a PetitMap of capacity 1"),
+        "{message}"
+    );
+
+    // Duplicates do not count towards the capacity
+    let result: Result<Compact<PetitSet<u32, 2>>, _> = from_str("[1,2,1]");
+    assert!(result.is_ok());
+}
+
+#[test]
+fn as_map_serde() {
+    let mut map: PetitMap<u32, &str, 5> = PetitMap::new();
+    map.insert(1, "one");
+    map.insert(5, "five");
+    map.insert(3, "three");
+
+    map.remove(&5);
+
+    let intermediate_repr = to_string(&AsMap(map)).unwrap();
+    assert_eq!(intermediate_repr, "{1:\"one\",3:\"three\"}");
+
+    let AsMap(deserialized_map): AsMap<PetitMap<u32, &str, 5>> =
+        from_str(&intermediate_repr).unwrap();
+
+    assert_eq!(map, deserialized_map);
+}
+
+#[test]
+fn as_map_serde_overflow() {
+    let result: Result<AsMap<PetitMap<u32, u32, 2>>, _> = from_str("{1:1,2:2,3:3,4:4,5:5}");
+    let message = result.unwrap_err().to_string();
+    assert!(
+        message.contains("more than 2 distinct keys for a PetitMap of capacity 2"),
+        "{message}"
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SaveFile<const CAP: usize> {
+    #[serde(with = "petitset::compact")]
+    unlocked_levels: PetitSet<u8, CAP>,
+    #[serde(with = "petitset::compact")]
+    high_scores: PetitMap<u8, u32, CAP>,
+}
+
+#[test]
+fn compact_serde_with() {
+    let mut save: SaveFile<8> = SaveFile {
+        unlocked_levels: PetitSet::new(),
+        high_scores: PetitMap::new(),
+    };
+    save.unlocked_levels.insert_at(1, 5);
+    save.unlocked_levels.insert_at(2, 7);
+    save.high_scores.insert_at(1, 100, 3);
+
+    let intermediate_repr = to_string(&save).unwrap();
+    assert_eq!(
+        intermediate_repr,
+        "(unlocked_levels:[1,2],high_scores:[(1,100)])"
+    );
+
+    // The stored format does not depend on the capacity
+    let smaller: SaveFile<2> = from_str(&intermediate_repr).unwrap();
+    assert_eq!(
+        smaller.unlocked_levels,
+        PetitSet::<u8, 2>::from_iter([1, 2])
+    );
+    assert_eq!(smaller.high_scores.get(&1), Some(&100));
+
+    let larger: SaveFile<16> = from_str(&intermediate_repr).unwrap();
+    assert_eq!(larger.unlocked_levels.len(), 2);
+
+    let too_small: Result<SaveFile<1>, _> = from_str(&intermediate_repr);
+    assert!(too_small.is_err());
+}
+
+#[test]
+fn deserialize_in_place() {
+    let mut source: PetitMap<u32, String, 4> = PetitMap::new();
+    source.insert(1, "one".to_string());
+    source.insert(2, "two".to_string());
+    source.insert(3, "three".to_string());
+    source.remove(&2);
+    let intermediate_repr = to_string(&source).unwrap();
+
+    // Reuse a map whose stale contents should all be overwritten
+    let mut pooled: PetitMap<u32, String, 4> = PetitMap::new();
+    pooled.insert(7, "seven".to_string());
+    pooled.insert(8, "eight".to_string());
+    pooled.insert(9, "nine".to_string());
+    pooled.insert(10, "ten".to_string());
+
+    let mut deserializer = Deserializer::from_str(&intermediate_repr).unwrap();
+    PetitMap::deserialize_in_place(&mut deserializer, &mut pooled).unwrap();
+
+    let fresh: PetitMap<u32, String, 4> = from_str(&intermediate_repr).unwrap();
+    assert_eq!(pooled, fresh);
+    assert_eq!(pooled.len(), 2);
+    assert_eq!(pooled.get_at(1), None);
+    assert_eq!(pooled.get_at(3), None);
+
+    let source: PetitSet<u32, 4> = PetitSet::from_iter([5, 6]);
+    let intermediate_repr = to_string(&source).unwrap();
+
+    let mut pooled: PetitSet<u32, 4> = PetitSet::from_iter([1, 2, 3, 4]);
+    let mut deserializer = Deserializer::from_str(&intermediate_repr).unwrap();
+    PetitSet::deserialize_in_place(&mut deserializer, &mut pooled).unwrap();
+
+    let fresh: PetitSet<u32, 4> = from_str(&intermediate_repr).unwrap();
+    assert_eq!(pooled, fresh);
+    assert_eq!(pooled.len(), 2);
+}
@@ -0,0 +1,29 @@
+//! Conversions between [`PetitSet`] and arrayvec's [`ArrayVec`]
+#![cfg(feature = "arrayvec_compat")]
+
+// This module is behind a feature flag: make sure to use `cargo build --all-features` to check that it compiles!
+use crate::PetitSet;
+use arrayvec::ArrayVec;
+
+impl<T: Eq, const CAP: usize> From<ArrayVec<T, CAP>> for PetitSet<T, CAP> {
+    /// Converts the [`ArrayVec`] into a [`PetitSet`], discarding any duplicate elements
+    ///
+    /// Elements are stored contiguously, in the order of their first occurrence.
+    fn from(array_vec: ArrayVec<T, CAP>) -> Self {
+        let mut set = PetitSet::new();
+        for element in array_vec {
+            // An ArrayVec<T, CAP> cannot contain more than CAP distinct elements
+            set.insert(element);
+        }
+        set
+    }
+}
+
+impl<T: Eq, const CAP: usize> From<PetitSet<T, CAP>> for ArrayVec<T, CAP> {
+    /// Converts the [`PetitSet`] into an [`ArrayVec`], in slot order
+    ///
+    /// The elements are compacted: empty slots in the set are dropped rather than preserved.
+    fn from(set: PetitSet<T, CAP>) -> Self {
+        set.into_iter().collect()
+    }
+}
@@ -0,0 +1,48 @@
+//! Fallible, method-chaining alternatives to [`Iterator::collect`]
+
+use crate::{CapacityError, PetitMap, PetitSet};
+
+/// An extension trait for [`Iterator`], used to collect into a [`PetitSet`] or [`PetitMap`] without panicking
+///
+/// The [`FromIterator`] implementations for these types panic if the iterator has too many distinct elements;
+/// these methods return a [`CapacityError`] instead.
+///
+/// # Example
+/// ```rust
+/// use petitset::TryCollectPetit;
+///
+/// let set = [1, 2, 1, 3].into_iter().try_collect_set::<3>().unwrap();
+/// assert_eq!(set.len(), 3);
+///
+/// let failed = (0..10).try_collect_set::<3>();
+/// assert!(failed.is_err());
+/// ```
+pub trait TryCollectPetit: Iterator + Sized {
+    /// Collects the elements of this iterator into a [`PetitSet`], discarding duplicates
+    ///
+    /// Returns a [`CapacityError`] containing the set built so far and the element that did not fit,
+    /// as [`PetitSet::try_from_iter`] does.
+    fn try_collect_set<const CAP: usize>(
+        self,
+    ) -> Result<PetitSet<Self::Item, CAP>, CapacityError<(PetitSet<Self::Item, CAP>, Self::Item)>>
+    where
+        Self::Item: Eq,
+    {
+        PetitSet::try_from_iter(self)
+    }
+
+    /// Collects the key-value pairs of this iterator into a [`PetitMap`], overwriting the values of duplicate keys
+    ///
+    /// Returns a [`CapacityError`] containing the map built so far and the pair that did not fit,
+    /// as [`PetitMap::try_from_iter`] does.
+    fn try_collect_map<K: Eq, V, const CAP: usize>(
+        self,
+    ) -> Result<PetitMap<K, V, CAP>, CapacityError<(PetitMap<K, V, CAP>, (K, V))>>
+    where
+        Self: Iterator<Item = (K, V)>,
+    {
+        PetitMap::try_from_iter(self)
+    }
+}
+
+impl<I: Iterator> TryCollectPetit for I {}
@@ -0,0 +1,47 @@
+//! Order-independent hashing, used to keep [`Hash`] consistent with the order-independent [`PartialEq`] implementations
+
+use core::hash::{Hash, Hasher};
+
+/// A minimal FNV-1a [`Hasher`], used to hash individual elements without requiring `std`
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Feeds the items into `state` in a way that does not depend on the order in which they are visited
+///
+/// Each item is hashed separately, and the resulting hashes are combined with a commutative sum.
+pub(crate) fn hash_unordered<I, H>(items: I, state: &mut H)
+where
+    I: IntoIterator,
+    I::Item: Hash,
+    H: Hasher,
+{
+    let mut len: usize = 0;
+    let mut combined: u64 = 0;
+    for item in items {
+        let mut hasher = FnvHasher::default();
+        item.hash(&mut hasher);
+        combined = combined.wrapping_add(hasher.finish());
+        len += 1;
+    }
+
+    state.write_usize(len);
+    state.write_u64(combined);
+}
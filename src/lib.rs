@@ -8,13 +8,32 @@
 
 use core::fmt::{Debug, Formatter, Result};
 
+mod hash;
+
 mod map;
-pub use map::{PetitMap, SuccesfulMapInsertion};
+pub use map::{
+    Entry, FailedMapInsertion, InsertAtOutcome, OccupiedEntry, PetitMap, PetitMapDrain,
+    PetitMapIter, SuccesfulMapInsertion, VacantEntry,
+};
 
 mod set;
-pub use set::{PetitSet, SuccesfulSetInsertion};
+pub use set::{PetitSet, PetitSetDrain, PetitSetExtractIf, PetitSetIter, SuccesfulSetInsertion};
+
+mod macros;
+
+mod collect;
+pub use collect::TryCollectPetit;
 
 mod serde;
+#[cfg(feature = "serde_compat")]
+pub use crate::serde::{compact, AsMap, Compact};
+
+mod rayon;
+
+mod arrayvec;
+
+mod reflect;
+
 pub mod set_algebra;
 
 /// An error returned when attempting to insert into a full [`PetitSet`] or [`PetitMap`].
@@ -24,6 +43,71 @@ pub mod set_algebra;
 #[cfg_attr(feature = "thiserror_compat", derive(thiserror::Error))]
 pub struct CapacityError<T>(pub T);
 
+impl<T> CapacityError<T> {
+    /// Returns a reference to the contained value
+    pub fn element(&self) -> &T {
+        &self.0
+    }
+
+    /// Consumes the error, returning the contained value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Transforms the contained value, preserving the error
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> CapacityError<U> {
+        CapacityError(f(self.0))
+    }
+}
+
+impl<T, const CAP: usize> CapacityError<(PetitSet<T, CAP>, T)> {
+    /// Returns a reference to the partially-constructed set,
+    /// for errors returned by methods like [`PetitSet::try_from_iter`]
+    pub fn partial(&self) -> &PetitSet<T, CAP> {
+        &self.0 .0
+    }
+
+    /// Returns a reference to the element that could not be inserted,
+    /// for errors returned by methods like [`PetitSet::try_from_iter`]
+    pub fn rejected(&self) -> &T {
+        &self.0 .1
+    }
+
+    /// Consumes the error, returning the partially-constructed set and the element that could not be inserted
+    ///
+    /// # Example
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let error = PetitSet::<u8, 2>::try_from_iter([1, 2, 3, 4]).unwrap_err();
+    /// let (partial, rejected) = error.into_parts();
+    /// assert_eq!(partial, PetitSet::<u8, 2>::from_iter([1, 2]));
+    /// assert_eq!(rejected, 3);
+    /// ```
+    pub fn into_parts(self) -> (PetitSet<T, CAP>, T) {
+        self.0
+    }
+}
+
+impl<K, V, const CAP: usize> CapacityError<(PetitMap<K, V, CAP>, (K, V))> {
+    /// Returns a reference to the partially-constructed map,
+    /// for errors returned by methods like [`PetitMap::try_from_iter`]
+    pub fn partial(&self) -> &PetitMap<K, V, CAP> {
+        &self.0 .0
+    }
+
+    /// Returns a reference to the key-value pair that could not be inserted,
+    /// for errors returned by methods like [`PetitMap::try_from_iter`]
+    pub fn rejected(&self) -> &(K, V) {
+        &self.0 .1
+    }
+
+    /// Consumes the error, returning the partially-constructed map and the key-value pair that could not be inserted
+    pub fn into_parts(self) -> (PetitMap<K, V, CAP>, (K, V)) {
+        self.0
+    }
+}
+
 impl<T> Debug for CapacityError<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         f.debug_struct("A `PetitSet` or `PetitMap` has overflowed.")
@@ -37,3 +121,52 @@ impl<T> std::fmt::Display for CapacityError<T> {
         self::Debug::fmt(self, f)
     }
 }
+
+/// An error returned when attempting to construct a [`PetitSet`] or [`PetitMap`] from data with duplicate elements.
+///
+/// It contains the first element that was found to be a duplicate.
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "thiserror_compat", derive(thiserror::Error))]
+pub struct DuplicateError<T>(pub T);
+
+impl<T> Debug for DuplicateError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("A `PetitSet` or `PetitMap` would have contained duplicate elements.")
+            .finish()
+    }
+}
+
+#[cfg(feature = "thiserror_compat")]
+impl<T> std::fmt::Display for DuplicateError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self::Debug::fmt(self, f)
+    }
+}
+
+/// An error returned when attempting to insert an element at a specific index of a [`PetitSet`]
+/// when a matching element is already stored elsewhere.
+///
+/// It contains the element that could not be inserted, and the index of the matching element.
+#[derive(PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "thiserror_compat", derive(thiserror::Error))]
+pub struct AlreadyPresent<T> {
+    /// The element that could not be inserted
+    pub element: T,
+    /// The index at which the matching element is stored
+    pub index: usize,
+}
+
+impl<T> Debug for AlreadyPresent<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("A matching element is already present in the `PetitSet`.")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+#[cfg(feature = "thiserror_compat")]
+impl<T> std::fmt::Display for AlreadyPresent<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self::Debug::fmt(self, f)
+    }
+}
@@ -0,0 +1,73 @@
+//! Macros for constructing [`PetitSet`](crate::PetitSet) and [`PetitMap`](crate::PetitMap) literals
+
+/// Creates a [`PetitSet`](crate::PetitSet) containing the provided elements
+///
+/// The capacity of the set is the number of elements provided.
+/// Duplicate elements are discarded, so the resulting set may not be full.
+/// Elements are stored in the order they were provided.
+///
+/// # Example
+/// ```rust
+/// use petitset::{petitset, PetitSet};
+///
+/// let set: PetitSet<u8, 4> = petitset![1, 2, 3, 2];
+/// assert_eq!(set.len(), 3);
+/// assert_eq!(set.get_at(2), Some(&3));
+/// ```
+#[macro_export]
+macro_rules! petitset {
+    ($($element:expr),* $(,)?) => {{
+        let elements = [$($element),*];
+
+        // Ties the capacity of the set to the number of elements
+        fn new_with_capacity_of<T, const N: usize>(_elements: &[T; N]) -> $crate::PetitSet<T, N> {
+            $crate::PetitSet::new()
+        }
+
+        let mut set = new_with_capacity_of(&elements);
+        for element in elements {
+            // There is exactly enough capacity for every element, so this cannot overflow
+            set.insert(element);
+        }
+        set
+    }};
+}
+
+/// Creates a [`PetitMap`](crate::PetitMap) containing the provided key-value pairs
+///
+/// The capacity of the map is the number of pairs provided.
+/// Later values for duplicate keys overwrite earlier ones, so the resulting map may not be full.
+/// Pairs are stored in the order their keys were first provided.
+///
+/// # Example
+/// ```rust
+/// use petitset::{petitmap, PetitMap};
+///
+/// let map: PetitMap<&str, u8, 3> = petitmap! {
+///     "a" => 1,
+///     "b" => 2,
+///     "a" => 3,
+/// };
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map.get("a"), Some(&3));
+/// ```
+#[macro_export]
+macro_rules! petitmap {
+    ($($key:expr => $value:expr),* $(,)?) => {{
+        let pairs = [$(($key, $value)),*];
+
+        // Ties the capacity of the map to the number of pairs
+        fn new_with_capacity_of<K, V, const N: usize>(
+            _pairs: &[(K, V); N],
+        ) -> $crate::PetitMap<K, V, N> {
+            $crate::PetitMap::new()
+        }
+
+        let mut map = new_with_capacity_of(&pairs);
+        for (key, value) in pairs {
+            // There is exactly enough capacity for every pair, so this cannot overflow
+            map.insert(key, value);
+        }
+        map
+    }};
+}
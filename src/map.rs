@@ -1,7 +1,14 @@
 //! A module for the [`PetitMap`] data structure
 
-use crate::CapacityError;
-use core::mem::swap;
+use crate::hash::hash_unordered;
+use crate::{CapacityError, DuplicateError, PetitSet};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{FilterMap, FusedIterator};
+use core::mem::{replace, swap};
+use core::ops::{Index, IndexMut};
+use core::slice::{Iter, IterMut};
 
 /// A map-like data structure with a fixed maximum size
 ///
@@ -23,9 +30,47 @@ use core::mem::swap;
 ///
 /// The maximum size of this type is given by the const-generic type parameter `CAP`.
 /// Keys are guaranteed to be unique.
-#[derive(Clone, Debug, Hash)]
+///
+/// Like [`HashMap`](https://doc.rust-lang.org/std/collections/struct.HashMap.html),
+/// look-up methods accept any borrowed form of the key type,
+/// but [`Eq`] on the borrowed form must match [`Eq`] on the key type.
+///
+/// When both `K` and `V` are [`Copy`], so is the map.
+#[derive(Copy)]
 pub struct PetitMap<K, V, const CAP: usize> {
     pub(crate) storage: [Option<(K, V)>; CAP],
+    // The number of filled slots in `storage`, cached so that `len` is O(1)
+    len: usize,
+    // Every slot before this index is filled, so searches for empty slots can start here
+    next_empty_hint: usize,
+}
+
+impl<K: Clone, V: Clone, const CAP: usize> Clone for PetitMap<K, V, CAP> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+            len: self.len,
+            next_empty_hint: self.next_empty_hint,
+        }
+    }
+
+    /// Clones `source` into `self` slot by slot
+    ///
+    /// Where both maps have a filled slot, the existing key and value are reused via [`Clone::clone_from`],
+    /// which can avoid reallocating when they own heap data.
+    fn clone_from(&mut self, source: &Self) {
+        for (slot, source_slot) in self.storage.iter_mut().zip(source.storage.iter()) {
+            match (slot, source_slot) {
+                (Some((key, value)), Some((source_key, source_value))) => {
+                    key.clone_from(source_key);
+                    value.clone_from(source_value);
+                }
+                (slot, source_slot) => *slot = source_slot.clone(),
+            }
+        }
+        self.len = source.len;
+        self.next_empty_hint = source.next_empty_hint;
+    }
 }
 
 impl<K, V, const CAP: usize> Default for PetitMap<K, V, CAP> {
@@ -35,12 +80,20 @@ impl<K, V, const CAP: usize> Default for PetitMap<K, V, CAP> {
 }
 
 impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
+    /// The maximum number of elements that can be stored in this [`PetitMap`] type
+    ///
+    /// This is equal to the const-generic parameter `CAP`,
+    /// and can be used without an instance, e.g. to size other arrays.
+    pub const CAPACITY: usize = CAP;
+
     /// Create a new empty [`PetitMap`].
     ///
     /// The capacity is given by the generic parameter `CAP`.
     pub fn new() -> Self {
         PetitMap {
             storage: [(); CAP].map(|_| None),
+            len: 0,
+            next_empty_hint: 0,
         }
     }
 
@@ -49,9 +102,12 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
     /// Returns `Some((K, V))` if the index is in-bounds and has an element.
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
+    /// Panics if the provided index is not less than CAP.
     pub fn get_at(&self, index: usize) -> Option<(&K, &V)> {
-        assert!(index <= CAP);
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitMap of capacity {CAP}"
+        );
 
         if let Some((key, value)) = &self.storage[index] {
             Some((key, value))
@@ -65,9 +121,12 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
     /// Returns `Some((&mut K, &mut V))` if the index is in-bounds and has an element
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
+    /// Panics if the provided index is not less than CAP.
     pub fn get_at_mut(&mut self, index: usize) -> Option<(&mut K, &mut V)> {
-        assert!(index <= CAP);
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitMap of capacity {CAP}"
+        );
 
         if let Some((key, value)) = &mut self.storage[index] {
             Some((key, value))
@@ -76,12 +135,40 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
         }
     }
 
+    /// Returns a reference to the key-value pair in the first filled slot
+    ///
+    /// Returns `None` if the map is empty.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.get_at(self.next_filled_index(0)?)
+    }
+
+    /// Returns a mutable reference to the key-value pair in the first filled slot
+    ///
+    /// Returns `None` if the map is empty.
+    pub fn first_mut(&mut self) -> Option<(&mut K, &mut V)> {
+        self.get_at_mut(self.next_filled_index(0)?)
+    }
+
+    /// Returns a reference to the key-value pair in the last filled slot
+    ///
+    /// Returns `None` if the map is empty.
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.get_at(self.last_filled_index()?)
+    }
+
+    /// Returns a mutable reference to the key-value pair in the last filled slot
+    ///
+    /// Returns `None` if the map is empty.
+    pub fn last_mut(&mut self) -> Option<(&mut K, &mut V)> {
+        self.get_at_mut(self.last_filled_index()?)
+    }
+
     /// Removes the element at the provided index
     ///
     /// Returns true if an element was found
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
+    /// Panics if the provided index is not less than CAP.
     pub fn remove_at(&mut self, index: usize) -> bool {
         self.take_at(index).is_some()
     }
@@ -91,25 +178,99 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
     /// Returns `Some((K, V))` if the index was full.
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
+    /// Panics if the provided index is not less than CAP.
     #[must_use = "Use remove_at if the value is not needed."]
     pub fn take_at(&mut self, index: usize) -> Option<(K, V)> {
-        assert!(index <= CAP);
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitMap of capacity {CAP}"
+        );
 
-        if let Some((_key, _value)) = &self.storage[index] {
-            let mut removed = None;
-            swap(&mut removed, &mut self.storage[index]);
+        self.replace_slot(index, None)
+    }
 
-            removed
+    /// Replaces the contents of the slot at the provided index, keeping the cached length and empty-slot hint up to date
+    ///
+    /// Returns the previous contents of the slot.
+    /// All code that fills or empties a slot must go through this method (or reset the cached fields directly).
+    pub(crate) fn replace_slot(&mut self, index: usize, slot: Option<(K, V)>) -> Option<(K, V)> {
+        let filled = slot.is_some();
+        let removed = replace(&mut self.storage[index], slot);
+
+        if filled {
+            self.len += 1;
+            if index == self.next_empty_hint {
+                self.next_empty_hint += 1;
+            }
         } else {
-            None
+            self.next_empty_hint = self.next_empty_hint.min(index);
+        }
+        if removed.is_some() {
+            self.len -= 1;
         }
+        removed
     }
+
     /// Returns an iterator over the key value pairs
     pub fn iter(&self) -> impl Iterator<Item = &(K, V)> {
         self.storage.iter().filter_map(|e| e.as_ref())
     }
 
+    /// Returns an iterator over the indexes of the filled slots of the [`PetitMap`], in order
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.storage
+            .iter()
+            .enumerate()
+            .filter_map(|(index, e)| e.as_ref().map(|_| index))
+    }
+
+    /// Returns an iterator over the key value pairs, along with the index of the slot each pair is stored in
+    ///
+    /// Indexes are stable: they can be used with methods like [`PetitMap::remove_at`] later on.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &K, &V)> {
+        self.storage
+            .iter()
+            .enumerate()
+            .filter_map(|(index, e)| e.as_ref().map(|(k, v)| (index, k, v)))
+    }
+
+    /// Returns the number of key-value pairs that satisfy the predicate
+    pub fn count_where<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.iter().filter(|(k, v)| f(k, v)).count()
+    }
+
+    /// Returns `true` if any key-value pair satisfies the predicate
+    ///
+    /// Stops at the first pair that does. Returns `false` if the map is empty.
+    pub fn any<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.iter().any(|(k, v)| f(k, v))
+    }
+
+    /// Returns `true` if every key-value pair satisfies the predicate
+    ///
+    /// Stops at the first pair that does not. Returns `true` if the map is empty.
+    pub fn all<F>(&self, mut f: F) -> bool
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.iter().all(|(k, v)| f(k, v))
+    }
+
+    /// Returns an iterator over the key value pairs, with mutable access to the values
+    ///
+    /// Keys are only exposed immutably, as changing them could break uniqueness.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+        self.storage
+            .iter_mut()
+            .filter_map(|e| e.as_mut().map(|(k, v)| (&*k, v)))
+    }
+
     /// An iterator visiting all keys in in a first-in, first-out order
     pub fn keys(&self) -> impl Iterator<Item = &K> {
         self.iter().map(|(k, _v)| k)
@@ -132,6 +293,40 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
             .map(|(_k, v)| v)
     }
 
+    /// Does any key in the map correspond to the provided value?
+    ///
+    /// Values are not unique, so this scans every value until a match is found.
+    pub fn contains_value(&self, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        self.values().any(|existing_value| existing_value == value)
+    }
+
+    /// Returns a reference to the first key (in slot order) whose value equals the provided value
+    ///
+    /// This is a reverse lookup: it scans every slot, and is O(CAP).
+    pub fn find_by_value(&self, value: &V) -> Option<&K>
+    where
+        V: PartialEq,
+    {
+        self.iter()
+            .find(|&(_k, existing_value)| existing_value == value)
+            .map(|(k, _v)| k)
+    }
+
+    /// Feeds only the keys of the map into the provided [`Hasher`], ignoring the values
+    ///
+    /// Like the [`Hash`] implementation of the map, this is order-independent.
+    /// Maps with the same set of keys always hash equally, and match the hash of [`PetitMap::keys_set`].
+    /// The [`Hash`] implementation of [`PetitMap`] continues to hash both keys and values.
+    pub fn hash_keys_only<H: Hasher>(&self, state: &mut H)
+    where
+        K: Hash,
+    {
+        hash_unordered(self.keys(), state);
+    }
+
     /// Returns the index of the next filled slot, if any
     ///
     /// Returns None if the cursor is larger than CAP
@@ -143,6 +338,11 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
         (cursor..CAP).find(|&i| self.storage[i].is_some())
     }
 
+    /// Returns the index of the last filled slot, if any
+    pub(crate) fn last_filled_index(&self) -> Option<usize> {
+        (0..CAP).rev().find(|&i| self.storage[i].is_some())
+    }
+
     /// Returns the index of the next empty slot, if any
     ///
     /// Returns None if the cursor is larger than CAP
@@ -151,39 +351,92 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
             return None;
         }
 
-        (cursor..CAP).find(|&i| self.storage[i].is_none())
+        // All slots before the hint are known to be filled
+        let start = cursor.max(self.next_empty_hint);
+        (start..CAP).find(|&i| self.storage[i].is_none())
     }
 
     /// Returns the current number of key-value pairs in the [`PetitMap`]
+    #[must_use]
     pub fn len(&self) -> usize {
-        self.storage.iter().filter(|e| e.is_some()).count()
+        self.len
     }
 
     /// Returns the maximum number of elements that can be stored in the [`PetitMap`]
     pub const fn capacity(&self) -> usize {
-        CAP
+        Self::CAPACITY
     }
 
     /// Are there exactly 0 elements in the [`PetitMap`]?
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
     /// Are there exactly CAP elements in the [`PetitMap`]?
+    ///
+    /// This is O(1), as the length is cached.
+    /// If so, [`PetitMap::try_insert`] will fail for any key that is not already present.
+    #[must_use]
     pub fn is_full(&self) -> bool {
         self.len() == CAP
     }
 
+    /// Returns the number of key-value pairs that can be added before the [`PetitMap`] is full
+    pub fn remaining_capacity(&self) -> usize {
+        CAP - self.len()
+    }
+
+    /// Can `additional` new keys be inserted without overflowing the [`PetitMap`]?
+    ///
+    /// Keys that are already present do not take up additional space,
+    /// so this is a conservative check when some of the keys may be duplicates.
+    pub fn can_fit(&self, additional: usize) -> bool {
+        additional <= self.remaining_capacity()
+    }
+
     /// Swaps the element in `index_a` with the element in `index_b`
     ///
     /// # Panics
     ///
-    /// Panics if either index is greater than CAP.
+    /// Panics if either index is not less than CAP.
     pub fn swap_at(&mut self, index_a: usize, index_b: usize) {
-        assert!(index_a <= CAP);
-        assert!(index_b <= CAP);
+        assert!(
+            index_a < CAP,
+            "index {index_a} out of bounds for PetitMap of capacity {CAP}"
+        );
+        assert!(
+            index_b < CAP,
+            "index {index_b} out of bounds for PetitMap of capacity {CAP}"
+        );
 
         self.storage.swap(index_a, index_b);
+        for index in [index_a, index_b] {
+            if self.storage[index].is_none() {
+                self.next_empty_hint = self.next_empty_hint.min(index);
+            }
+        }
+    }
+
+    /// Keeps only the first `max_len` key-value pairs, in slot order, removing the rest
+    ///
+    /// Retained pairs are never moved to a new slot.
+    /// If `max_len` is at least [`PetitMap::len`], this has no effect.
+    pub fn truncate(&mut self, max_len: usize) {
+        if max_len >= self.len() {
+            return;
+        }
+
+        let mut kept = 0;
+        for index in 0..CAP {
+            if self.storage[index].is_some() {
+                if kept < max_len {
+                    kept += 1;
+                } else {
+                    self.remove_at(index);
+                }
+            }
+        }
     }
 
     /// Removes all elements from the map without de-allocation
@@ -191,6 +444,105 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
         for index in 0..CAP {
             self.storage[index] = None;
         }
+        self.len = 0;
+        self.next_empty_hint = 0;
+    }
+
+    /// Moves all key-value pairs into a new [`PetitMap`] with a capacity of `NEW_CAP`
+    ///
+    /// If every key-value pair fits within the new capacity at its current index, indexes are preserved.
+    /// Otherwise, the pairs are compacted to the front of the new map, preserving their order.
+    ///
+    /// Returns a [`CapacityError`] containing the unchanged map if it has more than `NEW_CAP` key-value pairs.
+    pub fn into_capacity<const NEW_CAP: usize>(
+        mut self,
+    ) -> Result<PetitMap<K, V, NEW_CAP>, CapacityError<Self>> {
+        if self.len() > NEW_CAP {
+            return Err(CapacityError(self));
+        }
+
+        let slots_fit = match self.last_filled_index() {
+            Some(index) => index < NEW_CAP,
+            None => true,
+        };
+        if !slots_fit {
+            self.compact();
+        }
+
+        let mut new_map = PetitMap::new();
+        for index in 0..CAP {
+            if let Some(pair) = self.take_at(index) {
+                new_map.replace_slot(index, Some(pair));
+            }
+        }
+
+        Ok(new_map)
+    }
+
+    /// Compacts all key-value pairs into a new [`PetitMap`] with a capacity of `NEW_CAP`
+    ///
+    /// Unlike [`PetitMap::into_capacity`], slot positions are never preserved:
+    /// the pairs are always moved to the front of the new map, preserving their order.
+    /// Use [`PetitMap::len`] to pick the tightest capacity that will fit.
+    ///
+    /// Returns a [`CapacityError`] containing the first key-value pair that did not fit if the map has more than `NEW_CAP` pairs.
+    /// The remaining pairs are dropped.
+    pub fn shrink<const NEW_CAP: usize>(
+        self,
+    ) -> Result<PetitMap<K, V, NEW_CAP>, CapacityError<(K, V)>> {
+        let mut new_map = PetitMap::new();
+        for (index, pair) in self.storage.into_iter().flatten().enumerate() {
+            if index >= NEW_CAP {
+                return Err(CapacityError(pair));
+            }
+            new_map.replace_slot(index, Some(pair));
+        }
+
+        Ok(new_map)
+    }
+
+    /// Removes and returns the key-value pair in the last filled slot
+    ///
+    /// Returns `None` if the map is empty.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let index = self.last_filled_index()?;
+        self.take_at(index)
+    }
+
+    /// Removes and returns the key-value pair in the first filled slot
+    ///
+    /// Returns `None` if the map is empty.
+    pub fn pop_first(&mut self) -> Option<(K, V)> {
+        let index = self.next_filled_index(0)?;
+        self.take_at(index)
+    }
+
+    /// Shifts all key-value pairs towards the front of the map, removing any gaps
+    ///
+    /// The relative order of the pairs is preserved, and all trailing slots are left empty.
+    ///
+    /// Returns the number of key-value pairs in the map.
+    pub fn compact(&mut self) -> usize {
+        let mut next_index = 0;
+        for index in 0..CAP {
+            if self.storage[index].is_some() {
+                self.storage.swap(index, next_index);
+                next_index += 1;
+            }
+        }
+        self.next_empty_hint = next_index;
+        next_index
+    }
+
+    /// Removes all key-value pairs from the map, returning them as an iterator
+    ///
+    /// Pairs are yielded in slot order.
+    /// The map is left empty when the iterator is dropped, even if it was not fully consumed.
+    pub fn drain(&mut self) -> PetitMapDrain<'_, K, V, CAP> {
+        PetitMapDrain {
+            map: self,
+            cursor: 0,
+        }
     }
 
     /// Inserts a key-value pair into the next empty index of the map,
@@ -203,7 +555,7 @@ impl<K, V, const CAP: usize> PetitMap<K, V, CAP> {
     /// Avoid it unless you are guaranteed by construction that no duplicates exist.
     pub fn insert_unchecked(&mut self, key: K, value: V) -> Option<usize> {
         let index = self.next_empty_index(0)?;
-        self.storage[index] = Some((key, value));
+        self.replace_slot(index, Some((key, value)));
 
         Some(index)
     }
@@ -228,13 +580,34 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
             // Returns the old value, as the data was swapped
             Ok(SuccesfulMapInsertion::ExtantKey(value, index))
         } else if let Some(index) = self.next_empty_index(0) {
-            self.storage[index] = Some((key, value));
+            self.replace_slot(index, Some((key, value)));
             Ok(SuccesfulMapInsertion::NovelKey(index))
         } else {
             Err(CapacityError((key, value)))
         }
     }
 
+    /// Attempts to store the value into the map, but only if the key is not already present
+    ///
+    /// Unlike [`PetitMap::try_insert`], existing values are never overwritten.
+    ///
+    /// Returns the index at which the key-value pair was stored,
+    /// or a [`FailedMapInsertion`] containing the key-value pair that could not be inserted.
+    pub fn try_insert_if_absent(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<usize, FailedMapInsertion<K, V>> {
+        if let Some(index) = self.find(&key) {
+            Err(FailedMapInsertion::ExtantKey((key, value), index))
+        } else if let Some(index) = self.next_empty_index(0) {
+            self.replace_slot(index, Some((key, value)));
+            Ok(index)
+        } else {
+            Err(CapacityError((key, value)).into())
+        }
+    }
+
     /// Stores the value in the map, which can be looked up by the key
     ///
     /// Returns a [`SuccesfulMapInsertion`], which encodes both
@@ -248,36 +621,188 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
             .expect("Inserting this key-value pair would have overflowed the map!")
     }
 
+    /// Returns a [`PetitSet`] containing clones of the keys of this map
+    ///
+    /// Each key is stored at the same index it has in the map.
+    pub fn keys_set(&self) -> PetitSet<K, CAP>
+    where
+        K: Clone,
+    {
+        PetitSet::from_raw_array_unchecked(core::array::from_fn(|index| {
+            self.storage[index].as_ref().map(|(k, _v)| k.clone())
+        }))
+    }
+
+    /// Returns a [`PetitSet`] containing clones of every key whose value equals the provided value
+    ///
+    /// Each key is stored at the same index it has in the map.
+    /// Like [`PetitMap::find_by_value`], this scans every slot, and is O(CAP).
+    pub fn keys_for_value(&self, value: &V) -> PetitSet<K, CAP>
+    where
+        K: Clone,
+        V: PartialEq,
+    {
+        PetitSet::from_raw_array_unchecked(core::array::from_fn(|index| {
+            match &self.storage[index] {
+                Some((k, existing_value)) if existing_value == value => Some(k.clone()),
+                _ => None,
+            }
+        }))
+    }
+
+    /// Returns a [`PetitSet`] containing clones of the distinct values of this map
+    ///
+    /// Values may be repeated, so duplicates are discarded: only the first copy of each value (in slot order) is kept.
+    /// Values are packed into the first available slots, in order.
+    pub fn values_set(&self) -> PetitSet<V, CAP>
+    where
+        V: Eq + Clone,
+    {
+        self.values().cloned().collect()
+    }
+
+    /// Gets the [`Entry`] for the provided key, for in-place manipulation
+    ///
+    /// # Example
+    /// ```rust
+    /// use petitset::PetitMap;
+    ///
+    /// let mut map: PetitMap<&str, usize, 4> = PetitMap::new();
+    /// *map.entry("apple").or_insert(0) += 1;
+    /// *map.entry("apple").or_insert(0) += 1;
+    /// map.entry("banana").or_insert_with_key(|key| key.len());
+    ///
+    /// assert_eq!(map.get("apple"), Some(&2));
+    /// assert_eq!(map.get("banana"), Some(&6));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, CAP> {
+        match self.find(&key) {
+            Some(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+
+    /// Attempts to get a mutable reference to the value corresponding to the key,
+    /// inserting the value created by `make` if the key is not present.
+    ///
+    /// `make` is only called if the key is not present and there is room to insert it.
+    ///
+    /// Returns a [`CapacityError`] containing the key if it was not present and the map is full.
+    pub fn try_get_or_insert_with<F>(&mut self, key: K, make: F) -> Result<&mut V, CapacityError<K>>
+    where
+        F: FnOnce() -> V,
+    {
+        let index = match self.find(&key) {
+            Some(index) => index,
+            None => match self.next_empty_index(0) {
+                Some(index) => {
+                    self.replace_slot(index, Some((key, make())));
+                    index
+                }
+                None => return Err(CapacityError(key)),
+            },
+        };
+
+        Ok(self.get_at_mut(index).unwrap().1)
+    }
+
+    /// Gets a mutable reference to the value corresponding to the key,
+    /// inserting the value created by `make` if the key is not present.
+    ///
+    /// `make` is only called if the key is not present.
+    ///
+    /// # Panics
+    /// Panics if the map is full and the key is not present
+    pub fn get_or_insert_with<F>(&mut self, key: K, make: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        self.try_get_or_insert_with(key, make)
+            .expect("Inserting this key-value pair would have overflowed the map!")
+    }
+
+    /// Moves all key-value pairs from `other` into `self`, in order
+    ///
+    /// Keys that are already present in `self` will have their values overwritten by the values from `other`.
+    ///
+    /// Returns a [`CapacityError`] containing the first key-value pair that could not fit.
+    /// All pairs before it will have been moved into `self`, while any pairs after it are dropped.
+    pub fn append<const OTHER_CAP: usize>(
+        &mut self,
+        other: PetitMap<K, V, OTHER_CAP>,
+    ) -> Result<(), CapacityError<(K, V)>> {
+        for (key, value) in other {
+            self.try_insert(key, value)?;
+        }
+        Ok(())
+    }
+
     /// Insert a new key-value pair at the provided index
     ///
-    /// If a matching key already existed in the set, it will be moved to the supplied index.
+    /// If a matching key already existed in the map, it will be moved to the supplied index, keeping its existing value.
     /// Any key-value pair that was previously there will be moved to the matching key's original index.
     ///
-    /// Returns `Some((K, V))` of any element removed by this operation.
+    /// Returns an [`InsertAtOutcome`] describing which of these cases occurred.
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
-    pub fn insert_at(&mut self, key: K, value: V, index: usize) -> Option<(K, V)> {
-        assert!(index <= CAP);
+    /// Panics if the provided index is not less than CAP.
+    pub fn insert_at(&mut self, key: K, value: V, index: usize) -> InsertAtOutcome<K, V> {
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitMap of capacity {CAP}"
+        );
 
         if let Some(old_index) = self.find(&key) {
             self.swap_at(old_index, index);
-            None
-        } else if self.get_at(index).is_some() {
-            let removed = self.take_at(index);
-            self.storage[index] = Some((key, value));
-            removed
+            InsertAtOutcome::MovedExisting { from: old_index }
         } else {
-            self.storage[index] = Some((key, value));
-            None
+            match self.replace_slot(index, Some((key, value))) {
+                Some(displaced) => InsertAtOutcome::Displaced(displaced),
+                None => InsertAtOutcome::Placed,
+            }
+        }
+    }
+
+    /// Creates a consuming iterator visiting all keys in a first-in, first-out order
+    ///
+    /// The item type is `K`
+    pub fn into_keys(self) -> impl Iterator<Item = K> {
+        self.into_iter().map(|(k, _v)| k)
+    }
+
+    /// Creates a consuming iterator visiting all values in a first-in, first-out order
+    ///
+    /// The item type is `V`
+    pub fn into_values(self) -> impl Iterator<Item = V> {
+        self.into_iter().map(|(_k, v)| v)
+    }
+
+    /// Consumes the map, applying `f` to each value to produce a new [`PetitMap`]
+    ///
+    /// Keys are moved unchanged, and every key-value pair stays in the same slot.
+    pub fn map_values<V2, F>(self, mut f: F) -> PetitMap<K, V2, CAP>
+    where
+        F: FnMut(V) -> V2,
+    {
+        PetitMap {
+            storage: self.storage.map(|slot| slot.map(|(k, v)| (k, f(v)))),
+            len: self.len,
+            next_empty_hint: self.next_empty_hint,
         }
     }
 
     /// Returns the index for the provided key, if it exists in the map
-    pub fn find(&self, key: &K) -> Option<usize> {
+    ///
+    /// This is the inverse of [`PetitMap::get_at`].
+    #[doc(alias = "position_of", alias = "index_of")]
+    pub fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         for index in 0..CAP {
             if let Some((existing_key, _val)) = &self.storage[index] {
-                if *key == *existing_key {
+                if existing_key.borrow() == key {
                     return Some(index);
                 }
             }
@@ -286,14 +811,22 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
     }
 
     /// Does the map contain the provided key?
-    pub fn contains_key(&self, key: &K) -> bool {
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         self.find(key).is_some()
     }
 
     /// Returns a reference to the value corresponding to the key.
     ///
     /// Returns `Some(&V)` if the key is found
-    pub fn get(&self, key: &K) -> Option<&V> {
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         if let Some(index) = self.find(key) {
             if let Some((_key, value)) = &self.storage[index] {
                 return Some(value);
@@ -305,7 +838,11 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
     /// Returns the key-value pair corresponding to the supplied key.
     ///
     /// Returns `Some(&K, &V)` if the key is found
-    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         if let Some(index) = self.find(key) {
             if let Some((key, value)) = &self.storage[index] {
                 return Some((key, value));
@@ -314,10 +851,32 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
         None
     }
 
+    /// Returns the key-value pair corresponding to the supplied key, with a mutable reference to the value.
+    ///
+    /// The key cannot be mutated, as changing it could break the uniqueness of the keys.
+    ///
+    /// Returns `Some(&K, &mut V)` if the key is found
+    pub fn get_key_value_mut<Q>(&mut self, key: &Q) -> Option<(&K, &mut V)>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        if let Some((key, value)) = &mut self.storage[index] {
+            Some((&*key, value))
+        } else {
+            None
+        }
+    }
+
     /// Returns a mutable reference to the value corresponding to the key.
     ///
     ///  Returns `Some(&mut V)` if the key is found
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         if let Some(index) = self.find(key) {
             if let Some((_key, value)) = &mut self.storage[index] {
                 return Some(value);
@@ -326,10 +885,61 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
         None
     }
 
+    /// Applies `f` to the value corresponding to the key, if any.
+    ///
+    /// Returns `true` if the key was found and `f` was called, and `false` otherwise.
+    pub fn update<Q, F>(&mut self, key: &Q, f: F) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+        F: FnOnce(&mut V),
+    {
+        match self.find(key) {
+            Some(index) => {
+                let (_key, value) = self.get_at_mut(index).unwrap();
+                f(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns mutable references to the values corresponding to each of the `N` keys at once.
+    ///
+    /// Returns `None` if any of the keys are missing, or if any two keys are equal.
+    pub fn get_disjoint_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> Option<[&mut V; N]>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let mut indices = [0; N];
+        for (i, key) in keys.iter().enumerate() {
+            let index = self.find(key)?;
+            // Returning two mutable references to the same value would be unsound
+            if indices[..i].contains(&index) {
+                return None;
+            }
+            indices[i] = index;
+        }
+
+        let mut values: [Option<&mut V>; N] = [(); N].map(|_| None);
+        for (index, slot) in self.storage.iter_mut().enumerate() {
+            if let Some(i) = indices.iter().position(|&found| found == index) {
+                values[i] = slot.as_mut().map(|(_k, v)| v);
+            }
+        }
+
+        Some(values.map(|value| value.unwrap()))
+    }
+
     /// Removes the key-value pair from the map if the key is found
     ///
     /// Returns `Some((index))` if it was found
-    pub fn remove(&mut self, key: &K) -> Option<usize> {
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         if let Some(index) = self.find(key) {
             // We know this is valid, because we just found the right index
             self.remove_at(index);
@@ -343,7 +953,11 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
     ///
     /// Returns `Some((index, (K,V)))` if it was found
     #[must_use = "Use remove if the value is not needed."]
-    pub fn take(&mut self, key: &K) -> Option<(usize, (K, V))> {
+    pub fn take<Q>(&mut self, key: &Q) -> Option<(usize, (K, V))>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         if let Some(index) = self.find(key) {
             let result = self.take_at(index).map(|pair| (index, pair));
             debug_assert!(result.is_some());
@@ -356,7 +970,11 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
     /// Swaps the positions of `element_a` with the position of `element_b`
     ///
     /// Returns true if both keys were found and successfully swapped.
-    pub fn swap(&mut self, key_a: &K, key_b: &K) -> bool {
+    pub fn swap<Q>(&mut self, key_a: &Q, key_b: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         if let (Some(index_a), Some(index_b)) = (self.find(key_a), self.find(key_b)) {
             self.swap_at(index_a, index_b);
             true
@@ -368,19 +986,40 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all pairs (k, v) such that f(&k, &mut v) returns false. The elements are visited in order.
+    /// Retained elements are never moved to a new slot.
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&K, &mut V) -> bool,
     {
         for i in 0..self.capacity() {
             if let Some((k, v)) = self.get_at_mut(i) {
-                if f(k, v) {
+                if !f(k, v) {
                     self.remove_at(i);
                 }
             }
         }
     }
 
+    /// Retains only the pairs whose key satisfies the predicate.
+    ///
+    /// A convenience wrapper around [`PetitMap::retain`]: retained pairs are never moved to a new slot.
+    pub fn retain_keys<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K) -> bool,
+    {
+        self.retain(|k, _v| f(k));
+    }
+
+    /// Retains only the pairs whose value satisfies the predicate.
+    ///
+    /// A convenience wrapper around [`PetitMap::retain`]: retained pairs are never moved to a new slot.
+    pub fn retain_values<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&V) -> bool,
+    {
+        self.retain(|_k, v| f(v));
+    }
+
     /// Constructs a new [`PetitMap`] by consuming values from an iterator.
     ///
     /// The consumed values will be stored in order, with duplicate elements discarded.
@@ -415,12 +1054,103 @@ impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
         Ok(map)
     }
 
+    /// Construct a [`PetitMap`] from a fully-populated array of key-value pairs, checking for duplicate keys.
+    ///
+    /// Each pair is stored at the same index it had in the array.
+    ///
+    /// Returns a [`DuplicateError`] containing the first pair whose key was already present.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petitset::DuplicateError;
+    /// use petitset::PetitMap;
+    ///
+    /// let map = PetitMap::try_from_array([(1, 11), (2, 21), (3, 31)]);
+    /// assert_eq!(map, Ok(PetitMap::from_raw_array_unchecked([Some((1, 11)), Some((2, 21)), Some((3, 31))])));
+    ///
+    /// let failed = PetitMap::try_from_array([(1, 11), (2, 21), (1, 12)]);
+    /// assert_eq!(failed, Err(DuplicateError((1, 12))));
+    /// ```
+    pub fn try_from_array(pairs: [(K, V); CAP]) -> Result<Self, DuplicateError<(K, V)>> {
+        let mut map = Self::new();
+
+        for (key, value) in pairs {
+            if map.contains_key(&key) {
+                return Err(DuplicateError((key, value)));
+            }
+            // We have checked for uniqueness, and there are exactly CAP pairs
+            map.insert_unchecked(key, value);
+        }
+
+        Ok(map)
+    }
+
     /// Construct a [`PetitMap`] directly from an array, without checking for duplicates.
     ///
     /// It is a logic error if the keys of any two non-`None` values in the array are equal, as keys are expected to be unique.
     /// If this occurs, the [`PetitMap`] returned may behave unpredictably.
     pub fn from_raw_array_unchecked(values: [Option<(K, V)>; CAP]) -> Self {
-        Self { storage: values }
+        let len = values.iter().filter(|e| e.is_some()).count();
+        Self {
+            storage: values,
+            len,
+            next_empty_hint: 0,
+        }
+    }
+
+    /// Returns a reference to the underlying array of slots
+    ///
+    /// This exposes the internal layout of the map: empty slots are `None`,
+    /// and each key-value pair is stored at its index.
+    pub fn as_array(&self) -> &[Option<(K, V)>; CAP] {
+        &self.storage
+    }
+
+    /// Consumes the map, returning the underlying array of slots
+    ///
+    /// This is the inverse of [`PetitMap::from_raw_array_unchecked`], and exposes the internal layout of the map:
+    /// empty slots are `None`, and each key-value pair is stored at its index.
+    pub fn into_array(self) -> [Option<(K, V)>; CAP] {
+        self.storage
+    }
+}
+
+impl<K, V, const CAP: usize> AsRef<[Option<(K, V)>]> for PetitMap<K, V, CAP> {
+    /// Returns the underlying slots as a slice of length `CAP`
+    ///
+    /// Like [`PetitMap::as_array`], this includes the empty (`None`) slots.
+    fn as_ref(&self) -> &[Option<(K, V)>] {
+        &self.storage
+    }
+}
+
+impl<K, Q, V, const CAP: usize> Index<&Q> for PetitMap<K, V, CAP>
+where
+    K: Eq + Borrow<Q>,
+    Q: Eq + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    /// Panics if the key is not present in the [`PetitMap`].
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in PetitMap")
+    }
+}
+
+impl<K, Q, V, const CAP: usize> IndexMut<&Q> for PetitMap<K, V, CAP>
+where
+    K: Eq + Borrow<Q>,
+    Q: Eq + ?Sized,
+{
+    /// Returns a mutable reference to the value corresponding to the supplied key.
+    ///
+    /// # Panics
+    /// Panics if the key is not present in the [`PetitMap`].
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("key not found in PetitMap")
     }
 }
 
@@ -438,6 +1168,18 @@ impl<K: Eq, V, const CAP: usize> Extend<(K, V)> for PetitMap<K, V, CAP> {
     }
 }
 
+impl<'a, K: Eq + Copy, V: Copy, const CAP: usize> Extend<(&'a K, &'a V)> for PetitMap<K, V, CAP> {
+    /// Inserts copies of multiple new key-value pairs to the map.
+    ///
+    /// Duplicate keys will overwrite existing values.
+    ///
+    /// # Panics
+    /// Panics if the map would overflow due to the insertion of non-duplicate keys
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
+    }
+}
+
 impl<K: Eq, V: PartialEq, const CAP: usize> PetitMap<K, V, CAP> {
     /// Are the two [`PetitMap`]s element-for-element identical, in the same order?
     pub fn identical(&self, other: Self) -> bool {
@@ -452,11 +1194,48 @@ impl<K: Eq, V: PartialEq, const CAP: usize> PetitMap<K, V, CAP> {
 
 impl<K: Eq, V, const CAP: usize> FromIterator<(K, V)> for PetitMap<K, V, CAP> {
     /// Panics if the iterator contains more than `CAP` distinct elements.
+    /// Use [`PetitMap::try_from_iter`] or [`TryCollectPetit::try_collect_map`](crate::TryCollectPetit::try_collect_map)
+    /// when the length of the iterator is not trusted.
     fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
         PetitMap::try_from_iter(iter).unwrap()
     }
 }
 
+impl<K: Eq + Clone, V: Clone, const CAP: usize> TryFrom<&[(K, V)]> for PetitMap<K, V, CAP> {
+    type Error = CapacityError<(Self, (K, V))>;
+
+    /// Constructs a new [`PetitMap`] by cloning the key-value pairs of a slice.
+    ///
+    /// See [`PetitMap::try_from_iter`] for more details.
+    fn try_from(slice: &[(K, V)]) -> Result<Self, Self::Error> {
+        PetitMap::try_from_iter(slice.iter().cloned())
+    }
+}
+
+impl<K: Eq, V, const N: usize, const CAP: usize> TryFrom<[(K, V); N]> for PetitMap<K, V, CAP> {
+    type Error = CapacityError<(Self, (K, V))>;
+
+    /// Constructs a new [`PetitMap`] from an array of key-value pairs of any length.
+    ///
+    /// Unlike [`PetitMap::try_from_array`], pairs are packed into the first available slots.
+    /// See [`PetitMap::try_from_iter`] for more details.
+    fn try_from(array: [(K, V); N]) -> Result<Self, Self::Error> {
+        PetitMap::try_from_iter(array)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq, V, const CAP: usize> TryFrom<Vec<(K, V)>> for PetitMap<K, V, CAP> {
+    type Error = CapacityError<(Self, (K, V))>;
+
+    /// Constructs a new [`PetitMap`] by moving the key-value pairs out of a [`Vec`].
+    ///
+    /// See [`PetitMap::try_from_iter`] for more details.
+    fn try_from(vec: Vec<(K, V)>) -> Result<Self, Self::Error> {
+        PetitMap::try_from_iter(vec)
+    }
+}
+
 impl<K: Eq, V, const CAP: usize> IntoIterator for PetitMap<K, V, CAP> {
     type Item = (K, V);
     type IntoIter = PetitMapIter<K, V, CAP>;
@@ -468,6 +1247,28 @@ impl<K: Eq, V, const CAP: usize> IntoIterator for PetitMap<K, V, CAP> {
     }
 }
 
+impl<'a, K, V, const CAP: usize> IntoIterator for &'a PetitMap<K, V, CAP> {
+    type Item = (&'a K, &'a V);
+    type IntoIter =
+        FilterMap<Iter<'a, Option<(K, V)>>, fn(&'a Option<(K, V)>) -> Option<Self::Item>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.storage
+            .iter()
+            .filter_map(|e| e.as_ref().map(|(k, v)| (k, v)))
+    }
+}
+
+impl<'a, K, V, const CAP: usize> IntoIterator for &'a mut PetitMap<K, V, CAP> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter =
+        FilterMap<IterMut<'a, Option<(K, V)>>, fn(&'a mut Option<(K, V)>) -> Option<Self::Item>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.storage
+            .iter_mut()
+            .filter_map(|e| e.as_mut().map(|(k, v)| (&*k, v)))
+    }
+}
+
 /// An [`Iterator`] struct for [`PetitMap`]
 #[derive(Clone, Debug)]
 pub struct PetitMapIter<K: Eq, V, const CAP: usize> {
@@ -478,7 +1279,8 @@ pub struct PetitMapIter<K: Eq, V, const CAP: usize> {
 impl<K: Eq, V, const CAP: usize> PetitMapIter<K, V, CAP> {
     /// Converts this iterator into the underlying [`PetitMap`]
     ///
-    /// Simpler and more direct than using `.collect()`
+    /// Simpler and more direct than using `.collect()`.
+    /// Only the key-value pairs that have not yet been yielded remain, each in its original slot.
     #[must_use]
     pub fn into_map(self) -> PetitMap<K, V, CAP> {
         self.map
@@ -497,6 +1299,65 @@ impl<K: Eq, V, const CAP: usize> Iterator for PetitMapIter<K, V, CAP> {
             None
         }
     }
+
+    /// Elements are removed as they are yielded, so the remaining length is exact
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.map.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<K: Eq, V, const CAP: usize> ExactSizeIterator for PetitMapIter<K, V, CAP> {}
+
+impl<K: Eq, V, const CAP: usize> FusedIterator for PetitMapIter<K, V, CAP> {}
+
+impl<K: Eq, V, const CAP: usize> Default for PetitMapIter<K, V, CAP> {
+    /// Creates an iterator over an empty [`PetitMap`], which yields nothing
+    ///
+    /// [`PetitMapIter::into_map`] can be used to recover the (empty) map.
+    fn default() -> Self {
+        Self {
+            map: PetitMap::default(),
+            cursor: 0,
+        }
+    }
+}
+
+/// A draining [`Iterator`] struct for [`PetitMap`], created by [`PetitMap::drain`]
+#[derive(Debug)]
+pub struct PetitMapDrain<'a, K, V, const CAP: usize> {
+    map: &'a mut PetitMap<K, V, CAP>,
+    cursor: usize,
+}
+
+impl<'a, K, V, const CAP: usize> Iterator for PetitMapDrain<'a, K, V, CAP> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(index) = self.map.next_filled_index(self.cursor) {
+            self.cursor = index + 1;
+            self.map.take_at(index)
+        } else {
+            self.cursor = CAP;
+            None
+        }
+    }
+
+    /// Elements are removed as they are yielded, so the remaining length is exact
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.map.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K, V, const CAP: usize> ExactSizeIterator for PetitMapDrain<'a, K, V, CAP> {}
+
+impl<'a, K, V, const CAP: usize> FusedIterator for PetitMapDrain<'a, K, V, CAP> {}
+
+impl<'a, K, V, const CAP: usize> Drop for PetitMapDrain<'a, K, V, CAP> {
+    fn drop(&mut self) {
+        self.map.clear();
+    }
 }
 
 impl<K: Eq, V: PartialEq, const CAP: usize, const OTHER_CAP: usize>
@@ -509,6 +1370,11 @@ impl<K: Eq, V: PartialEq, const CAP: usize, const OTHER_CAP: usize>
     ///
     /// Uses an inefficient O(n^2) algorithm due to minimal trait bounds.
     fn eq(&self, other: &PetitMap<K, V, OTHER_CAP>) -> bool {
+        // Two maps cannot be equal if their number of keys differs
+        if self.len() != other.len() {
+            return false;
+        }
+
         for key in self.keys() {
             if self.get(key) != other.get(key) {
                 return false;
@@ -520,6 +1386,47 @@ impl<K: Eq, V: PartialEq, const CAP: usize, const OTHER_CAP: usize>
 
 impl<K: Eq, V: Eq, const CAP: usize> Eq for PetitMap<K, V, CAP> {}
 
+impl<K: Hash, V: Hash, const CAP: usize> Hash for PetitMap<K, V, CAP> {
+    /// Hashes the key-value pairs of the map
+    ///
+    /// Like [`PartialEq`], this is order-independent: equal maps always hash equally.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self.iter(), state);
+    }
+}
+
+impl<K: Debug, V: Debug, const CAP: usize> Debug for PetitMap<K, V, CAP> {
+    /// Formats the map as `{k1: v1, k2: v2}`, skipping empty slots
+    ///
+    /// With alternate formatting (`{:#?}`), each pair is instead listed under the index of its slot,
+    /// as `{0: (k1, v1), 3: (k2, v2)}`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_map()
+                .entries(self.iter_indexed().map(|(index, k, v)| (index, (k, v))))
+                .finish()
+        } else {
+            f.debug_map()
+                .entries(self.iter().map(|(k, v)| (k, v)))
+                .finish()
+        }
+    }
+}
+
+impl<K: Display, V: Display, const CAP: usize> Display for PetitMap<K, V, CAP> {
+    /// Formats the map as `{k1: v1, k2: v2}`, skipping empty slots
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{key}: {value}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
 /// The `Ok` result of a successful [`PetitMap`] insertion operation
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SuccesfulMapInsertion<V> {
@@ -528,3 +1435,199 @@ pub enum SuccesfulMapInsertion<V> {
     /// The key already existed, so the old value and the index were returned
     ExtantKey(V, usize),
 }
+
+/// The result of a [`PetitMap::insert_at`] operation
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InsertAtOutcome<K, V> {
+    /// The key was new and the slot was empty: the key-value pair was stored at the provided index
+    Placed,
+    /// The key was new, and the key-value pair that previously occupied the slot was removed and returned
+    Displaced((K, V)),
+    /// The key already existed at index `from`, and was swapped into the provided index
+    ///
+    /// Any key-value pair that previously occupied the provided index is now stored at `from`.
+    MovedExisting {
+        /// The index at which the matching key was previously stored
+        from: usize,
+    },
+}
+
+/// The `Err` result of a failed [`PetitMap::try_insert_if_absent`] operation
+///
+/// Both variants contain the key-value pair that could not be inserted.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FailedMapInsertion<K, V> {
+    /// The key already existed at the provided index, so the map was left unchanged
+    ExtantKey((K, V), usize),
+    /// The key was new, but the map was full
+    Overfull(CapacityError<(K, V)>),
+}
+
+impl<K, V> From<CapacityError<(K, V)>> for FailedMapInsertion<K, V> {
+    fn from(error: CapacityError<(K, V)>) -> Self {
+        FailedMapInsertion::Overfull(error)
+    }
+}
+
+/// A view into a single slot of a [`PetitMap`], which may be either vacant or occupied
+///
+/// Created by [`PetitMap::entry`].
+#[derive(Debug)]
+pub enum Entry<'a, K, V, const CAP: usize> {
+    /// The key is already present in the map
+    Occupied(OccupiedEntry<'a, K, V, CAP>),
+    /// The key is not present in the map
+    Vacant(VacantEntry<'a, K, V, CAP>),
+}
+
+impl<'a, K, V, const CAP: usize> Entry<'a, K, V, CAP> {
+    /// Returns a reference to the key of this entry
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, then returns a mutable reference to the value
+    ///
+    /// # Panics
+    /// Panics if the entry is vacant and the map is full.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `f` if the entry is vacant, then returns a mutable reference to the value
+    ///
+    /// `f` is only called if the entry is vacant.
+    ///
+    /// # Panics
+    /// Panics if the entry is vacant and the map is full.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        self.or_insert_with_key(|_key| f())
+    }
+
+    /// Inserts the result of `f` if the entry is vacant, then returns a mutable reference to the value
+    ///
+    /// `f` is passed a reference to the key, and is only called if the entry is vacant.
+    ///
+    /// # Panics
+    /// Panics if the entry is vacant and the map is full.
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = f(entry.key());
+                entry.insert(value)
+            }
+        }
+    }
+
+    /// Calls `f` on the value if the entry is occupied, then returns the entry
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V: Default, const CAP: usize> Entry<'a, K, V, CAP> {
+    /// Inserts the default value if the entry is vacant, then returns a mutable reference to the value
+    ///
+    /// # Panics
+    /// Panics if the entry is vacant and the map is full.
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// A view into an occupied slot of a [`PetitMap`], as part of an [`Entry`]
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, K, V, const CAP: usize> {
+    map: &'a mut PetitMap<K, V, CAP>,
+    index: usize,
+}
+
+impl<'a, K, V, const CAP: usize> OccupiedEntry<'a, K, V, CAP> {
+    /// Returns the index of the slot that this entry occupies
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a reference to the key of this entry
+    pub fn key(&self) -> &K {
+        self.map.get_at(self.index).unwrap().0
+    }
+
+    /// Returns a reference to the value of this entry
+    pub fn get(&self) -> &V {
+        self.map.get_at(self.index).unwrap().1
+    }
+
+    /// Returns a mutable reference to the value of this entry
+    pub fn get_mut(&mut self) -> &mut V {
+        self.map.get_at_mut(self.index).unwrap().1
+    }
+
+    /// Converts the entry into a mutable reference to its value, with the lifetime of the map
+    pub fn into_mut(self) -> &'a mut V {
+        self.map.get_at_mut(self.index).unwrap().1
+    }
+
+    /// Replaces the value of this entry, returning the old value
+    pub fn insert(&mut self, value: V) -> V {
+        replace(self.get_mut(), value)
+    }
+
+    /// Removes the key-value pair from the map, returning the value
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Removes the key-value pair from the map, returning both the key and the value
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.take_at(self.index).unwrap()
+    }
+}
+
+/// A view into a vacant slot of a [`PetitMap`], as part of an [`Entry`]
+#[derive(Debug)]
+pub struct VacantEntry<'a, K, V, const CAP: usize> {
+    map: &'a mut PetitMap<K, V, CAP>,
+    key: K,
+}
+
+impl<'a, K, V, const CAP: usize> VacantEntry<'a, K, V, CAP> {
+    /// Returns a reference to the key that would be inserted
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes ownership of the key, leaving the map unchanged
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Attempts to insert the value into the next empty slot of the map, returning a mutable reference to it
+    ///
+    /// Returns a [`CapacityError`] containing the key-value pair if the map is full.
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, CapacityError<(K, V)>> {
+        match self.map.next_empty_index(0) {
+            Some(index) => {
+                self.map.replace_slot(index, Some((self.key, value)));
+                Ok(self.map.get_at_mut(index).unwrap().1)
+            }
+            None => Err(CapacityError((self.key, value))),
+        }
+    }
+
+    /// Inserts the value into the next empty slot of the map, returning a mutable reference to it
+    ///
+    /// # Panics
+    /// Panics if the map is full.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.try_insert(value)
+            .expect("Inserting this key-value pair would have overflowed the map!")
+    }
+}
@@ -0,0 +1,58 @@
+//! Implementations of rayon's [`IntoParallelIterator`] trait
+#![cfg(feature = "rayon_compat")]
+
+// This module is behind a feature flag: make sure to use `cargo build --all-features` to check that it compiles!
+use crate::{PetitMap, PetitSet};
+use rayon::{
+    array,
+    iter::{FilterMap, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator},
+    slice,
+};
+
+// The storage array is contiguous, so it can be split cleanly: empty slots are filtered out afterwards.
+impl<K: Send, V: Send, const CAP: usize> IntoParallelIterator for PetitMap<K, V, CAP> {
+    type Item = (K, V);
+    type Iter =
+        FilterMap<array::IntoIter<Option<(K, V)>, CAP>, fn(Option<(K, V)>) -> Option<(K, V)>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.storage.into_par_iter().filter_map(|e| e)
+    }
+}
+
+impl<'a, K: Sync, V: Sync, const CAP: usize> IntoParallelIterator for &'a PetitMap<K, V, CAP> {
+    type Item = (&'a K, &'a V);
+    type Iter =
+        FilterMap<slice::Iter<'a, Option<(K, V)>>, fn(&'a Option<(K, V)>) -> Option<Self::Item>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.storage
+            .par_iter()
+            .filter_map(|e| e.as_ref().map(|(k, v)| (k, v)))
+    }
+}
+
+impl<T: Send, const CAP: usize> IntoParallelIterator for PetitSet<T, CAP> {
+    type Item = T;
+    type Iter = FilterMap<array::IntoIter<Option<(T, ())>, CAP>, fn(Option<(T, ())>) -> Option<T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.map
+            .storage
+            .into_par_iter()
+            .filter_map(|e| e.map(|(k, _v)| k))
+    }
+}
+
+impl<'a, T: Sync, const CAP: usize> IntoParallelIterator for &'a PetitSet<T, CAP> {
+    type Item = &'a T;
+    type Iter =
+        FilterMap<slice::Iter<'a, Option<(T, ())>>, fn(&'a Option<(T, ())>) -> Option<&'a T>>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.map
+            .storage
+            .par_iter()
+            .filter_map(|e| e.as_ref().map(|(k, _v)| k))
+    }
+}
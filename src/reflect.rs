@@ -0,0 +1,627 @@
+//! Implementations of `bevy_reflect`'s [`Reflect`] and [`FromReflect`] traits
+//!
+//! [`PetitSet`] is reflected as a [`List`], and [`PetitMap`] as a [`Map`].
+//! List indexes are dense: index `n` refers to the `n`th filled slot, skipping any empty slots.
+#![cfg(feature = "bevy_reflect_compat")]
+
+// This module is behind a feature flag: make sure to use `cargo build --all-features` to check that it compiles!
+use crate::{PetitMap, PetitSet, SuccesfulMapInsertion};
+use bevy_reflect::{
+    impl_type_path, map_partial_eq, utility::GenericTypeInfoCell, ApplyError, FromReflect,
+    FromType, Generics, GetTypeRegistration, List, ListInfo, ListIter, Map, MapInfo, MaybeTyped,
+    PartialReflect, Reflect, ReflectCloneError, ReflectFromPtr, ReflectFromReflect, ReflectKind,
+    ReflectMut, ReflectOwned, ReflectRef, TypeInfo, TypeParamInfo, TypePath, TypeRegistration,
+    TypeRegistry, Typed,
+};
+use std::any::Any;
+
+impl_type_path!((in petitset) PetitSet<T, const CAP: usize>);
+impl_type_path!((in petitset) PetitMap<K, V, const CAP: usize>);
+
+impl<T, const CAP: usize> PetitSet<T, CAP> {
+    /// Returns the slot index of the `n`th element, skipping empty slots
+    fn nth_filled_index(&self, n: usize) -> Option<usize> {
+        (0..CAP).filter(|&i| self.get_at(i).is_some()).nth(n)
+    }
+}
+
+/// The error returned when a reflected value cannot be converted into `T`
+fn mismatched_types<T: TypePath>(value: &dyn PartialReflect) -> ApplyError {
+    ApplyError::MismatchedTypes {
+        from_type: value.reflect_type_path().into(),
+        to_type: T::type_path().into(),
+    }
+}
+
+/// The error returned when applying a patch would overflow a collection of capacity `CAP`
+fn overflowed<const CAP: usize>(from_size: usize) -> ApplyError {
+    ApplyError::DifferentSize {
+        from_size,
+        to_size: CAP,
+    }
+}
+
+/// Index-based operations address the `n`th filled element of the set, skipping empty slots.
+///
+/// As a [`PetitSet`] cannot store duplicates, these operations are not exactly those of a `Vec`:
+/// [`List::insert`] ignores its index, and [`List::push`] discards elements that are already present.
+/// Uniqueness is not enforced for elements mutated through [`List::get_mut`]:
+/// prefer [`PartialReflect::apply`], which rebuilds the set and discards duplicates.
+impl<T, const CAP: usize> List for PetitSet<T, CAP>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+{
+    fn get(&self, index: usize) -> Option<&dyn PartialReflect> {
+        self.iter()
+            .nth(index)
+            .map(|element| element as &dyn PartialReflect)
+    }
+
+    /// Elements must not be mutated so that they become equal to another element in the set.
+    fn get_mut(&mut self, index: usize) -> Option<&mut dyn PartialReflect> {
+        self.iter_mut()
+            .nth(index)
+            .map(|element| element as &mut dyn PartialReflect)
+    }
+
+    /// Inserts the element into the set, exactly as [`PetitSet::insert`] does.
+    ///
+    /// As elements of a [`PetitSet`] are never shifted, the `index` is ignored.
+    ///
+    /// # Panics
+    /// Panics if the value is not a valid `T`, or if the set is full.
+    fn insert(&mut self, _index: usize, element: Box<dyn PartialReflect>) {
+        self.push(element);
+    }
+
+    /// # Panics
+    /// Panics if `index` is not less than the number of elements.
+    fn remove(&mut self, index: usize) -> Box<dyn PartialReflect> {
+        let len = PetitSet::len(self);
+        let slot = self
+            .nth_filled_index(index)
+            .unwrap_or_else(|| panic!("index {index} out of bounds for PetitSet of length {len}"));
+
+        Box::new(self.take_at(slot).unwrap())
+    }
+
+    /// Inserts the element into the set, exactly as [`PetitSet::insert`] does.
+    ///
+    /// # Panics
+    /// Panics if the value is not a valid `T`, or if the set is full.
+    fn push(&mut self, value: Box<dyn PartialReflect>) {
+        let value = T::take_from_reflect(value).unwrap_or_else(|value| {
+            panic!(
+                "Attempted to push invalid value of type {}.",
+                value.reflect_type_path()
+            )
+        });
+        PetitSet::insert(self, value);
+    }
+
+    fn pop(&mut self) -> Option<Box<dyn PartialReflect>> {
+        PetitSet::pop(self).map(|element| Box::new(element) as Box<dyn PartialReflect>)
+    }
+
+    fn len(&self) -> usize {
+        PetitSet::len(self)
+    }
+
+    fn iter(&self) -> ListIter<'_> {
+        ListIter::new(self)
+    }
+
+    fn drain(&mut self) -> Vec<Box<dyn PartialReflect>> {
+        PetitSet::drain(self)
+            .map(|element| Box::new(element) as Box<dyn PartialReflect>)
+            .collect()
+    }
+}
+
+impl<T, const CAP: usize> PartialReflect for PetitSet<T, CAP>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+{
+    fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+        Some(<Self as Typed>::type_info())
+    }
+
+    fn into_partial_reflect(self: Box<Self>) -> Box<dyn PartialReflect> {
+        self
+    }
+
+    fn as_partial_reflect(&self) -> &dyn PartialReflect {
+        self
+    }
+
+    fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+        self
+    }
+
+    fn try_into_reflect(self: Box<Self>) -> Result<Box<dyn Reflect>, Box<dyn PartialReflect>> {
+        Ok(self)
+    }
+
+    fn try_as_reflect(&self) -> Option<&dyn Reflect> {
+        Some(self)
+    }
+
+    fn try_as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+        Some(self)
+    }
+
+    /// Replaces the contents of the set with the elements of the provided list
+    ///
+    /// # Panics
+    /// Panics if [`PartialReflect::try_apply`] would fail.
+    fn apply(&mut self, value: &dyn PartialReflect) {
+        if let Err(err) = PartialReflect::try_apply(self, value) {
+            panic!("{err}");
+        }
+    }
+
+    /// Replaces the contents of the set with the elements of the provided list
+    ///
+    /// Duplicate elements are discarded, so the set never contains two equal elements.
+    /// If an error is returned, the set is left unchanged.
+    ///
+    /// Returns [`ApplyError::DifferentSize`] if the list contains more than `CAP` distinct elements.
+    fn try_apply(&mut self, value: &dyn PartialReflect) -> Result<(), ApplyError> {
+        let list = value.reflect_ref().as_list()?;
+
+        let mut patched = PetitSet::new();
+        for element in list.iter() {
+            let element = T::from_reflect(element).ok_or_else(|| mismatched_types::<T>(element))?;
+            patched
+                .try_insert(element)
+                .map_err(|_| overflowed::<CAP>(list.len()))?;
+        }
+
+        *self = patched;
+        Ok(())
+    }
+
+    fn reflect_kind(&self) -> ReflectKind {
+        ReflectKind::List
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::List(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::List(self)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::List(self)
+    }
+
+    /// Clones each element into the same slot
+    fn reflect_clone(&self) -> Result<Box<dyn Reflect>, ReflectCloneError> {
+        let mut set = PetitSet::<T, CAP>::new();
+        for (index, slot) in self.map.storage.iter().enumerate() {
+            if let Some((element, ())) = slot {
+                set.map
+                    .replace_slot(index, Some((element.reflect_clone_and_take()?, ())));
+            }
+        }
+
+        Ok(Box::new(set))
+    }
+
+    /// Tests set-equality against any reflected list
+    ///
+    /// Like the [`PartialEq`] implementation for [`PetitSet`], this is order-independent.
+    fn reflect_partial_eq(&self, value: &dyn PartialReflect) -> Option<bool> {
+        let Ok(list) = value.reflect_ref().as_list() else {
+            return Some(false);
+        };
+
+        if PetitSet::len(self) != list.len() {
+            return Some(false);
+        }
+
+        for element in PetitSet::iter(self) {
+            let mut match_found = false;
+            for other_element in list.iter() {
+                if element.reflect_partial_eq(other_element)? {
+                    match_found = true;
+                    break;
+                }
+            }
+
+            if !match_found {
+                return Some(false);
+            }
+        }
+
+        Some(true)
+    }
+}
+
+impl<T, const CAP: usize> Reflect for PetitSet<T, CAP>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+}
+
+impl<T, const CAP: usize> Typed for PetitSet<T, CAP>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+{
+    fn type_info() -> &'static TypeInfo {
+        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            TypeInfo::List(
+                ListInfo::new::<Self, T>()
+                    .with_generics(Generics::from_iter([TypeParamInfo::new::<T>("T")])),
+            )
+        })
+    }
+}
+
+impl<T, const CAP: usize> GetTypeRegistration for PetitSet<T, CAP>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+{
+    fn get_type_registration() -> TypeRegistration {
+        let mut registration = TypeRegistration::of::<Self>();
+        registration.insert::<ReflectFromPtr>(FromType::<Self>::from_type());
+        registration.insert::<ReflectFromReflect>(FromType::<Self>::from_type());
+        registration
+    }
+
+    fn register_type_dependencies(registry: &mut TypeRegistry) {
+        registry.register::<T>();
+    }
+}
+
+impl<T, const CAP: usize> FromReflect for PetitSet<T, CAP>
+where
+    T: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+{
+    /// Returns `None` if the list contains more than `CAP` distinct elements
+    fn from_reflect(reflect: &dyn PartialReflect) -> Option<Self> {
+        let list = reflect.reflect_ref().as_list().ok()?;
+
+        let mut set = PetitSet::new();
+        for element in list.iter() {
+            set.try_insert(T::from_reflect(element)?).ok()?;
+        }
+
+        Some(set)
+    }
+}
+
+impl<K, V, const CAP: usize> Map for PetitMap<K, V, CAP>
+where
+    K: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+    V: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+{
+    fn get(&self, key: &dyn PartialReflect) -> Option<&dyn PartialReflect> {
+        key.try_downcast_ref::<K>()
+            .and_then(|key| PetitMap::get(self, key))
+            .map(|value| value as &dyn PartialReflect)
+    }
+
+    fn get_mut(&mut self, key: &dyn PartialReflect) -> Option<&mut dyn PartialReflect> {
+        key.try_downcast_ref::<K>()
+            .and_then(move |key| PetitMap::get_mut(self, key))
+            .map(|value| value as &mut dyn PartialReflect)
+    }
+
+    fn len(&self) -> usize {
+        PetitMap::len(self)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (&dyn PartialReflect, &dyn PartialReflect)> + '_> {
+        Box::new(
+            PetitMap::iter(self).map(|(k, v)| (k as &dyn PartialReflect, v as &dyn PartialReflect)),
+        )
+    }
+
+    fn drain(&mut self) -> Vec<(Box<dyn PartialReflect>, Box<dyn PartialReflect>)> {
+        PetitMap::drain(self)
+            .map(|(key, value)| {
+                (
+                    Box::new(key) as Box<dyn PartialReflect>,
+                    Box::new(value) as Box<dyn PartialReflect>,
+                )
+            })
+            .collect()
+    }
+
+    fn retain(&mut self, f: &mut dyn FnMut(&dyn PartialReflect, &mut dyn PartialReflect) -> bool) {
+        PetitMap::retain(self, move |key, value| f(key, value));
+    }
+
+    /// Inserts the key-value pair into the map, exactly as [`PetitMap::insert`] does.
+    ///
+    /// # Panics
+    /// Panics if the key or value are not valid, or if a new key is inserted into a full map.
+    fn insert_boxed(
+        &mut self,
+        key: Box<dyn PartialReflect>,
+        value: Box<dyn PartialReflect>,
+    ) -> Option<Box<dyn PartialReflect>> {
+        let key = K::take_from_reflect(key).unwrap_or_else(|key| {
+            panic!(
+                "Attempted to insert invalid key of type {}.",
+                key.reflect_type_path()
+            )
+        });
+        let value = V::take_from_reflect(value).unwrap_or_else(|value| {
+            panic!(
+                "Attempted to insert invalid value of type {}.",
+                value.reflect_type_path()
+            )
+        });
+
+        match PetitMap::insert(self, key, value) {
+            SuccesfulMapInsertion::NovelKey(_) => None,
+            SuccesfulMapInsertion::ExtantKey(old_value, _) => {
+                Some(Box::new(old_value) as Box<dyn PartialReflect>)
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &dyn PartialReflect) -> Option<Box<dyn PartialReflect>> {
+        let mut from_reflect = None;
+        key.try_downcast_ref::<K>()
+            .or_else(|| {
+                from_reflect = K::from_reflect(key);
+                from_reflect.as_ref()
+            })
+            .and_then(|key| self.take(key))
+            .map(|(_index, (_key, value))| Box::new(value) as Box<dyn PartialReflect>)
+    }
+}
+
+impl<K, V, const CAP: usize> PartialReflect for PetitMap<K, V, CAP>
+where
+    K: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+    V: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+{
+    fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+        Some(<Self as Typed>::type_info())
+    }
+
+    fn into_partial_reflect(self: Box<Self>) -> Box<dyn PartialReflect> {
+        self
+    }
+
+    fn as_partial_reflect(&self) -> &dyn PartialReflect {
+        self
+    }
+
+    fn as_partial_reflect_mut(&mut self) -> &mut dyn PartialReflect {
+        self
+    }
+
+    fn try_into_reflect(self: Box<Self>) -> Result<Box<dyn Reflect>, Box<dyn PartialReflect>> {
+        Ok(self)
+    }
+
+    fn try_as_reflect(&self) -> Option<&dyn Reflect> {
+        Some(self)
+    }
+
+    fn try_as_reflect_mut(&mut self) -> Option<&mut dyn Reflect> {
+        Some(self)
+    }
+
+    /// Patches the map to match the provided map
+    ///
+    /// # Panics
+    /// Panics if [`PartialReflect::try_apply`] would fail.
+    fn apply(&mut self, value: &dyn PartialReflect) {
+        if let Err(err) = PartialReflect::try_apply(self, value) {
+            panic!("{err}");
+        }
+    }
+
+    /// Patches the map to match the provided map
+    ///
+    /// Existing values are patched in place, new key-value pairs are inserted,
+    /// and keys that are missing from `value` are removed.
+    ///
+    /// Every key and new value is converted, and the capacity is checked, before the map is modified:
+    /// if [`ApplyError::MismatchedTypes`] or [`ApplyError::DifferentSize`] is returned, the map is left unchanged.
+    /// However, an error from patching an existing value in place is returned as soon as it occurs,
+    /// so the map may be partially modified in that case.
+    ///
+    /// Returns [`ApplyError::DifferentSize`] if `value` contains more than `CAP` distinct keys.
+    fn try_apply(&mut self, value: &dyn PartialReflect) -> Result<(), ApplyError> {
+        let map_value = value.reflect_ref().as_map()?;
+        if map_value.len() > CAP {
+            return Err(overflowed::<CAP>(map_value.len()));
+        }
+
+        // Convert everything up front, so that conversion errors cannot leave the map half-patched.
+        // Pairs are stored as the key, the patch for an existing value, or a new value.
+        let mut pairs: Vec<(K, Result<&dyn PartialReflect, V>)> =
+            Vec::with_capacity(map_value.len());
+        for (key, b_value) in map_value.iter() {
+            let key = K::from_reflect(key).ok_or_else(|| mismatched_types::<K>(key))?;
+            if PetitMap::contains_key(self, &key) {
+                pairs.push((key, Ok(b_value)));
+            } else {
+                let b_value =
+                    V::from_reflect(b_value).ok_or_else(|| mismatched_types::<V>(b_value))?;
+                pairs.push((key, Err(b_value)));
+            }
+        }
+
+        for (key, patch) in &pairs {
+            if let Ok(b_value) = patch {
+                PetitMap::get_mut(self, key).unwrap().try_apply(*b_value)?;
+            }
+        }
+
+        // Remove stale keys before inserting, so that the new pairs are guaranteed to fit
+        PetitMap::retain(self, |key, _value| pairs.iter().any(|(k, _)| k == key));
+        for (key, patch) in pairs {
+            if let Err(b_value) = patch {
+                self.insert(key, b_value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reflect_kind(&self) -> ReflectKind {
+        ReflectKind::Map
+    }
+
+    fn reflect_ref(&self) -> ReflectRef<'_> {
+        ReflectRef::Map(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut<'_> {
+        ReflectMut::Map(self)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Map(self)
+    }
+
+    /// Clones each key-value pair into the same slot
+    fn reflect_clone(&self) -> Result<Box<dyn Reflect>, ReflectCloneError> {
+        let mut map = PetitMap::<K, V, CAP>::new();
+        for (index, slot) in self.storage.iter().enumerate() {
+            if let Some((key, value)) = slot {
+                let pair = (
+                    key.reflect_clone_and_take()?,
+                    value.reflect_clone_and_take()?,
+                );
+                map.replace_slot(index, Some(pair));
+            }
+        }
+
+        Ok(Box::new(map))
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn PartialReflect) -> Option<bool> {
+        map_partial_eq(self, value)
+    }
+}
+
+impl<K, V, const CAP: usize> Reflect for PetitMap<K, V, CAP>
+where
+    K: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+    V: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+}
+
+impl<K, V, const CAP: usize> Typed for PetitMap<K, V, CAP>
+where
+    K: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+    V: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+{
+    fn type_info() -> &'static TypeInfo {
+        static CELL: GenericTypeInfoCell = GenericTypeInfoCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            TypeInfo::Map(
+                MapInfo::new::<Self, K, V>().with_generics(Generics::from_iter([
+                    TypeParamInfo::new::<K>("K"),
+                    TypeParamInfo::new::<V>("V"),
+                ])),
+            )
+        })
+    }
+}
+
+impl<K, V, const CAP: usize> GetTypeRegistration for PetitMap<K, V, CAP>
+where
+    K: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+    V: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+{
+    fn get_type_registration() -> TypeRegistration {
+        let mut registration = TypeRegistration::of::<Self>();
+        registration.insert::<ReflectFromPtr>(FromType::<Self>::from_type());
+        registration.insert::<ReflectFromReflect>(FromType::<Self>::from_type());
+        registration
+    }
+
+    fn register_type_dependencies(registry: &mut TypeRegistry) {
+        registry.register::<K>();
+        registry.register::<V>();
+    }
+}
+
+impl<K, V, const CAP: usize> FromReflect for PetitMap<K, V, CAP>
+where
+    K: FromReflect + MaybeTyped + TypePath + GetTypeRegistration + Eq,
+    V: FromReflect + MaybeTyped + TypePath + GetTypeRegistration,
+{
+    /// Returns `None` if the map contains more than `CAP` distinct keys
+    fn from_reflect(reflect: &dyn PartialReflect) -> Option<Self> {
+        let map = reflect.reflect_ref().as_map().ok()?;
+
+        let mut new_map = PetitMap::new();
+        for (key, value) in map.iter() {
+            new_map
+                .try_insert(K::from_reflect(key)?, V::from_reflect(value)?)
+                .ok()?;
+        }
+
+        Some(new_map)
+    }
+}
@@ -3,13 +3,40 @@
 
 // This module is behind a feature flag: make sure to use `cargo build --all-features` to check that it compiles!
 use crate::{PetitMap, PetitSet};
+use core::fmt;
 use core::marker::PhantomData;
 use serde::{
-    de::{SeqAccess, Visitor},
-    ser::SerializeSeq,
+    de::{Error, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
     Deserialize, Serialize,
 };
-use std::fmt;
+
+/// A wrapper that (de)serializes a [`PetitSet`] or [`PetitMap`] as a flat sequence of its occupied entries
+///
+/// The default implementations preserve the exact slot of each entry, by serializing all `CAP` slots as [`Option`]s.
+/// Some formats (such as TOML) cannot represent the `None` values this produces.
+///
+/// This representation drops empty slots entirely, so exact slot positions are lost:
+/// when deserialized, entries are packed into the first available slots, in order.
+/// Set (or map) equality is preserved by a round-trip.
+///
+/// Because only the occupied entries are stored, this format is stable across changes to `CAP`:
+/// data can be deserialized into a collection of any capacity, so long as its entries fit.
+/// Deserialization fails if there are more than `CAP` distinct entries.
+///
+/// To use this representation for a field of a derived type, annotate it with `#[serde(with = "petitset::compact")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Compact<T>(pub T);
+
+/// A wrapper that (de)serializes a [`PetitMap`] as a true map of its occupied key-value pairs
+///
+/// This produces output like `{"a": 1, "b": 2}` in self-describing formats such as JSON.
+/// Like [`Compact`], exact slot positions are lost:
+/// when deserialized, pairs are inserted into the first available slots, in order.
+///
+/// Deserialization fails if the map contains more than `CAP` distinct keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AsMap<T>(pub T);
 
 mod petitmap {
     use super::*;
@@ -38,6 +65,42 @@ mod petitmap {
             // This should be deserialized as a sequence, or gaps will be lost
             deserializer.deserialize_seq(PetitMapVisitor::new())
         }
+
+        /// Clears `place`, then refills its slots from the serialized sequence
+        ///
+        /// This reuses the existing [`PetitMap`], rather than constructing a new one.
+        fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(PetitMapInPlaceVisitor(place))
+        }
+    }
+
+    /// Fills the slots of an empty `map` in order from the provided sequence
+    fn fill_map<'de, K, V, S, const CAP: usize>(
+        map: &mut PetitMap<K, V, CAP>,
+        mut access: S,
+    ) -> Result<(), S::Error>
+    where
+        K: Deserialize<'de> + Eq,
+        V: Deserialize<'de>,
+        S: SeqAccess<'de>,
+    {
+        for i in 0..CAP {
+            let next_element: Option<Option<(K, V)>> = access.next_element()?;
+
+            // Insert the next element found
+            if let Some(element) = next_element {
+                map.replace_slot(i, element);
+            } else {
+                // We have run out of items in the serialized format
+                // before we ran out of capacity.
+                break;
+            }
+        }
+
+        Ok(())
     }
 
     #[derive(Debug)]
@@ -65,26 +128,36 @@ mod petitmap {
         }
 
         /// Deserialize `PetitMap` from an abstract "sequence" provided by the `Deserializer`.
-        fn visit_seq<S>(self, mut access: S) -> Result<Self::Value, S::Error>
+        fn visit_seq<S>(self, access: S) -> Result<Self::Value, S::Error>
         where
             S: SeqAccess<'de>,
         {
             let mut map: PetitMap<K, V, CAP> = PetitMap::default();
+            fill_map(&mut map, access)?;
+            Ok(map)
+        }
+    }
 
-            for i in 0..CAP {
-                let next_element: Option<Option<(K, V)>> = access.next_element()?;
-
-                // Insert the next element found
-                if let Some(element) = next_element {
-                    map.storage[i] = element;
-                } else {
-                    // We have run out of items in the serialized format
-                    // before we ran out of capacity.
-                    break;
-                }
-            }
+    struct PetitMapInPlaceVisitor<'a, K, V, const CAP: usize>(&'a mut PetitMap<K, V, CAP>);
 
-            Ok(map)
+    impl<'a, 'de, K, V, const CAP: usize> Visitor<'de> for PetitMapInPlaceVisitor<'a, K, V, CAP>
+    where
+        K: Deserialize<'de> + Eq,
+        V: Deserialize<'de>,
+    {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of `Option<T>` values to create a PetitMap.")
+        }
+
+        /// Refill the existing `PetitMap` from an abstract "sequence" provided by the `Deserializer`.
+        fn visit_seq<S>(self, access: S) -> Result<Self::Value, S::Error>
+        where
+            S: SeqAccess<'de>,
+        {
+            self.0.clear();
+            fill_map(self.0, access)
         }
     }
 }
@@ -121,6 +194,42 @@ mod petitset {
         {
             deserializer.deserialize_seq(PetitSetVisitor::new())
         }
+
+        /// Clears `place`, then refills its slots from the serialized sequence
+        ///
+        /// This reuses the existing [`PetitSet`], rather than constructing a new one.
+        fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(PetitSetInPlaceVisitor(place))
+        }
+    }
+
+    /// Fills the slots of an empty `set` in order from the provided sequence
+    fn fill_set<'de, T, S, const CAP: usize>(
+        set: &mut PetitSet<T, CAP>,
+        mut access: S,
+    ) -> Result<(), S::Error>
+    where
+        T: Deserialize<'de> + Eq,
+        S: SeqAccess<'de>,
+    {
+        for i in 0..CAP {
+            let next_element: Option<Option<T>> = access.next_element()?;
+
+            // If another element was found in the serialized format
+            // process and insert it
+            if let Some(element) = next_element {
+                set.map.replace_slot(i, element.map(|e| (e, ())));
+            } else {
+                // We have run out of items in the serialized format
+                // before we ran out of capacity.
+                break;
+            }
+        }
+
+        Ok(())
     }
 
     #[derive(Debug)]
@@ -147,27 +256,292 @@ mod petitset {
         }
 
         /// Deserialize `PetitSet` from an abstract "sequence" provided by the `Deserializer`.
+        fn visit_seq<S>(self, access: S) -> Result<Self::Value, S::Error>
+        where
+            S: SeqAccess<'de>,
+        {
+            let mut set: PetitSet<T, CAP> = PetitSet::default();
+            fill_set(&mut set, access)?;
+            Ok(set)
+        }
+    }
+
+    struct PetitSetInPlaceVisitor<'a, T, const CAP: usize>(&'a mut PetitSet<T, CAP>);
+
+    impl<'a, 'de, T, const CAP: usize> Visitor<'de> for PetitSetInPlaceVisitor<'a, T, CAP>
+    where
+        T: Deserialize<'de> + Eq + Clone,
+    {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of `Option<T>` values to create a PetitSet.")
+        }
+
+        /// Refill the existing `PetitSet` from an abstract "sequence" provided by the `Deserializer`.
+        fn visit_seq<S>(self, access: S) -> Result<Self::Value, S::Error>
+        where
+            S: SeqAccess<'de>,
+        {
+            self.0.clear();
+            fill_set(self.0, access)
+        }
+    }
+}
+
+/// (De)serializes a [`PetitSet`] or [`PetitMap`] field using the [`Compact`] representation
+///
+/// Annotate a field of a type that derives `Serialize` and `Deserialize` with `#[serde(with = "petitset::compact")]`.
+/// See the `compact_serde_with` test in the `serde_tests` crate for a complete example.
+pub mod compact {
+    use super::*;
+
+    /// Serializes the occupied entries of a [`PetitSet`] or [`PetitMap`] as a flat sequence
+    pub fn serialize<'a, T, S>(value: &'a T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        Compact<&'a T>: Serialize,
+        S: serde::Serializer,
+    {
+        Compact(value).serialize(serializer)
+    }
+
+    /// Deserializes a [`PetitSet`] or [`PetitMap`] from a flat sequence of its entries
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        Compact<T>: Deserialize<'de>,
+        D: serde::Deserializer<'de>,
+    {
+        Compact::deserialize(deserializer).map(|Compact(value)| value)
+    }
+
+    impl<K: Serialize, V: Serialize, const CAP: usize> Serialize for Compact<PetitMap<K, V, CAP>> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            Compact(&self.0).serialize(serializer)
+        }
+    }
+
+    impl<K: Serialize, V: Serialize, const CAP: usize> Serialize for Compact<&PetitMap<K, V, CAP>> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+            for pair in self.0.iter() {
+                seq.serialize_element(pair)?;
+            }
+            seq.end()
+        }
+    }
+
+    impl<'de, K: Deserialize<'de> + Eq, V: Deserialize<'de>, const CAP: usize> Deserialize<'de>
+        for Compact<PetitMap<K, V, CAP>>
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(CompactPetitMapVisitor::new())
+        }
+    }
+
+    #[derive(Debug)]
+    struct CompactPetitMapVisitor<K, V, const CAP: usize> {
+        marker: PhantomData<fn() -> PetitMap<K, V, CAP>>,
+    }
+
+    impl<K, V, const CAP: usize> CompactPetitMapVisitor<K, V, CAP> {
+        fn new() -> Self {
+            CompactPetitMapVisitor {
+                marker: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, K, V, const CAP: usize> Visitor<'de> for CompactPetitMapVisitor<K, V, CAP>
+    where
+        K: Deserialize<'de> + Eq,
+        V: Deserialize<'de>,
+    {
+        type Value = Compact<PetitMap<K, V, CAP>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "a sequence of at most {CAP} key-value pairs to create a PetitMap."
+            )
+        }
+
+        /// Deserialize `PetitMap` from a sequence of key-value pairs, packing them into the first available slots.
+        fn visit_seq<S>(self, mut access: S) -> Result<Self::Value, S::Error>
+        where
+            S: SeqAccess<'de>,
+        {
+            let mut map: PetitMap<K, V, CAP> = PetitMap::default();
+
+            while let Some((key, value)) = access.next_element()? {
+                if map.try_insert(key, value).is_err() {
+                    return Err(S::Error::custom(format_args!(
+                        "more than {CAP} distinct keys for a PetitMap of capacity {CAP}"
+                    )));
+                }
+            }
+
+            Ok(Compact(map))
+        }
+    }
+
+    impl<T: Serialize, const CAP: usize> Serialize for Compact<PetitSet<T, CAP>> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            Compact(&self.0).serialize(serializer)
+        }
+    }
+
+    impl<T: Serialize, const CAP: usize> Serialize for Compact<&PetitSet<T, CAP>> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+            for element in self.0.iter() {
+                seq.serialize_element(element)?;
+            }
+            seq.end()
+        }
+    }
+
+    impl<'de, T: Deserialize<'de> + Eq, const CAP: usize> Deserialize<'de>
+        for Compact<PetitSet<T, CAP>>
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(CompactPetitSetVisitor::new())
+        }
+    }
+
+    #[derive(Debug)]
+    struct CompactPetitSetVisitor<T, const CAP: usize> {
+        marker: PhantomData<fn() -> PetitSet<T, CAP>>,
+    }
+
+    impl<T, const CAP: usize> CompactPetitSetVisitor<T, CAP> {
+        fn new() -> Self {
+            CompactPetitSetVisitor {
+                marker: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, T, const CAP: usize> Visitor<'de> for CompactPetitSetVisitor<T, CAP>
+    where
+        T: Deserialize<'de> + Eq,
+    {
+        type Value = Compact<PetitSet<T, CAP>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "a sequence of at most {CAP} distinct elements to create a PetitSet."
+            )
+        }
+
+        /// Deserialize `PetitSet` from a sequence of elements, packing them into the first available slots.
         fn visit_seq<S>(self, mut access: S) -> Result<Self::Value, S::Error>
         where
             S: SeqAccess<'de>,
         {
             let mut set: PetitSet<T, CAP> = PetitSet::default();
 
-            for i in 0..CAP {
-                let next_element: Option<Option<T>> = access.next_element()?;
-
-                // If another element was found in the serialized format
-                // process and insert it
-                if let Some(element) = next_element {
-                    set.map.storage[i] = element.map(|e| (e, ()));
-                } else {
-                    // We have run out of items in the serialized format
-                    // before we ran out of capacity.
-                    break;
+            while let Some(element) = access.next_element()? {
+                if set.try_insert(element).is_err() {
+                    return Err(S::Error::custom(format_args!(
+                        "more than {CAP} distinct elements for a PetitSet of capacity {CAP}"
+                    )));
                 }
             }
 
-            Ok(set)
+            Ok(Compact(set))
+        }
+    }
+}
+
+mod as_map {
+    use super::*;
+
+    impl<K: Serialize, V: Serialize, const CAP: usize> Serialize for AsMap<PetitMap<K, V, CAP>> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let mut map = serializer.serialize_map(Some(self.0.len()))?;
+            for (key, value) in self.0.iter() {
+                map.serialize_entry(key, value)?;
+            }
+            map.end()
+        }
+    }
+
+    impl<'de, K: Deserialize<'de> + Eq, V: Deserialize<'de>, const CAP: usize> Deserialize<'de>
+        for AsMap<PetitMap<K, V, CAP>>
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_map(AsMapVisitor::new())
+        }
+    }
+
+    #[derive(Debug)]
+    struct AsMapVisitor<K, V, const CAP: usize> {
+        marker: PhantomData<fn() -> PetitMap<K, V, CAP>>,
+    }
+
+    impl<K, V, const CAP: usize> AsMapVisitor<K, V, CAP> {
+        fn new() -> Self {
+            AsMapVisitor {
+                marker: PhantomData,
+            }
+        }
+    }
+
+    impl<'de, K, V, const CAP: usize> Visitor<'de> for AsMapVisitor<K, V, CAP>
+    where
+        K: Deserialize<'de> + Eq,
+        V: Deserialize<'de>,
+    {
+        type Value = AsMap<PetitMap<K, V, CAP>>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "a map with at most {CAP} entries to create a PetitMap."
+            )
+        }
+
+        /// Deserialize `PetitMap` from an abstract "map" provided by the `Deserializer`.
+        fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+        where
+            M: MapAccess<'de>,
+        {
+            let mut map: PetitMap<K, V, CAP> = PetitMap::default();
+
+            while let Some((key, value)) = access.next_entry()? {
+                if map.try_insert(key, value).is_err() {
+                    return Err(M::Error::custom(format_args!(
+                        "more than {CAP} distinct keys for a PetitMap of capacity {CAP}"
+                    )));
+                }
+            }
+
+            Ok(AsMap(map))
         }
     }
 }
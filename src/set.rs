@@ -1,7 +1,17 @@
 //! A module for the [`PetitSet`] data structure
 
+use crate::hash::hash_unordered;
+use crate::map::{InsertAtOutcome, PetitMapDrain};
 use crate::PetitMap;
-use crate::{map::SuccesfulMapInsertion, CapacityError};
+use crate::{map::SuccesfulMapInsertion, AlreadyPresent, CapacityError, DuplicateError};
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{FilterMap, FusedIterator};
+use core::mem::swap;
+use core::ops::{Index, IndexMut};
+use core::slice::{Iter, IterMut};
 
 /// A set-like data structure with a fixed maximum size
 ///
@@ -22,11 +32,32 @@ use crate::{map::SuccesfulMapInsertion, CapacityError};
 ///
 /// The maximum size of this type is given by the const-generic type parameter `CAP`.
 /// Entries in this structure are guaranteed to be unique.
-#[derive(Debug, Clone, Hash)]
+///
+/// Like [`HashSet`](https://doc.rust-lang.org/std/collections/struct.HashSet.html),
+/// look-up methods accept any borrowed form of the element type,
+/// but [`Eq`] on the borrowed form must match [`Eq`] on the element type.
+///
+/// When `T` is [`Copy`], so is the set.
+#[derive(Copy)]
 pub struct PetitSet<T, const CAP: usize> {
     pub(crate) map: PetitMap<T, (), CAP>,
 }
 
+impl<T: Clone, const CAP: usize> Clone for PetitSet<T, CAP> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+
+    /// Clones `source` into `self` slot by slot
+    ///
+    /// Where both sets have a filled slot, the existing element is reused via [`Clone::clone_from`].
+    fn clone_from(&mut self, source: &Self) {
+        self.map.clone_from(&source.map);
+    }
+}
+
 impl<T, const CAP: usize> Default for PetitSet<T, CAP> {
     fn default() -> Self {
         Self::new()
@@ -34,6 +65,12 @@ impl<T, const CAP: usize> Default for PetitSet<T, CAP> {
 }
 
 impl<T, const CAP: usize> PetitSet<T, CAP> {
+    /// The maximum number of elements that can be stored in this [`PetitSet`] type
+    ///
+    /// This is equal to the const-generic parameter `CAP`,
+    /// and can be used without an instance, e.g. to size other arrays.
+    pub const CAPACITY: usize = CAP;
+
     /// Create a new empty [`PetitSet`].
     ///
     /// The capacity is given by the generic parameter `CAP`.
@@ -59,75 +96,433 @@ impl<T, const CAP: usize> PetitSet<T, CAP> {
 
     /// Return the capacity of the [`PetitSet`]
     pub const fn capacity(&self) -> usize {
-        CAP
+        Self::CAPACITY
     }
 
     /// Returns the current number of elements in the [`PetitSet`]
+    #[must_use]
     pub fn len(&self) -> usize {
         self.map.len()
     }
 
     /// Are there exactly 0 elements in the [`PetitSet`]?
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         self.map.is_empty()
     }
 
     /// Are there exactly CAP elements in the [`PetitSet`]?
+    ///
+    /// This is O(1), as the length is cached.
+    /// If so, [`PetitSet::try_insert`] will fail for any element that is not already present.
+    #[must_use]
     pub fn is_full(&self) -> bool {
         self.map.is_full()
     }
 
+    /// Returns the number of elements that can be added before the [`PetitSet`] is full
+    pub fn remaining_capacity(&self) -> usize {
+        self.map.remaining_capacity()
+    }
+
+    /// Can `additional` new elements be inserted without overflowing the [`PetitSet`]?
+    ///
+    /// Elements that are already present do not take up additional space,
+    /// so this is a conservative check when some of the elements may be duplicates.
+    pub fn can_fit(&self, additional: usize) -> bool {
+        self.map.can_fit(additional)
+    }
+
+    /// Keeps only the first `max_len` elements, in slot order, removing the rest
+    ///
+    /// Retained elements are never moved to a new slot.
+    /// If `max_len` is at least [`PetitSet::len`], this has no effect.
+    pub fn truncate(&mut self, max_len: usize) {
+        self.map.truncate(max_len);
+    }
+
     /// Returns an iterator over the elements of the [`PetitSet`]
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.map.iter().map(|(k, _v)| k)
     }
 
+    /// Returns an iterator over the indexes of the filled slots of the [`PetitSet`], in order
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.map.indices()
+    }
+
+    /// Returns an iterator over the elements of the [`PetitSet`], along with the index of the slot each element is stored in
+    ///
+    /// Indexes are stable: they can be used with methods like [`PetitSet::remove_at`] later on.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.map.iter_indexed().map(|(index, k, _v)| (index, k))
+    }
+
+    /// Returns an iterator over the elements of the [`PetitSet`] in chunks of `size` elements, in slot order
+    ///
+    /// Empty slots are skipped, so every chunk but the last contains exactly `size` elements.
+    /// The final chunk may be shorter.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    #[cfg(feature = "std")]
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Vec<&T>> {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        let mut elements = self.iter();
+        core::iter::from_fn(move || {
+            let chunk: Vec<&T> = elements.by_ref().take(size).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        })
+    }
+
+    /// Returns the number of elements that satisfy the predicate
+    pub fn count_where<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter().filter(|element| f(element)).count()
+    }
+
+    /// Returns `true` if any element satisfies the predicate
+    ///
+    /// Stops at the first element that does. Returns `false` if the set is empty.
+    pub fn any<F>(&self, f: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter().any(f)
+    }
+
+    /// Returns `true` if every element satisfies the predicate
+    ///
+    /// Stops at the first element that does not. Returns `true` if the set is empty.
+    pub fn all<F>(&self, f: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.iter().all(f)
+    }
+
+    /// Returns an iterator over mutable references to the elements of the [`PetitSet`]
+    ///
+    /// Elements are visited in slot order.
+    ///
+    /// It is a logic error to mutate an element so that it becomes equal to any other element in the set,
+    /// as elements are expected to be unique.
+    /// If this occurs, the [`PetitSet`] may behave unpredictably.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.map
+            .storage
+            .iter_mut()
+            .filter_map(|e| e.as_mut())
+            .map(|(k, _v)| k)
+    }
+
+    /// Returns an iterator over mutable references to the elements of the [`PetitSet`],
+    /// along with the index of the slot each element is stored in
+    ///
+    /// Elements are visited in slot order, and indexes match those of [`PetitSet::iter_indexed`],
+    /// so they can be used to correlate elements with data stored outside of the set.
+    ///
+    /// It is a logic error to mutate an element so that it becomes equal to any other element in the set,
+    /// as elements are expected to be unique.
+    /// If this occurs, the [`PetitSet`] may behave unpredictably.
+    pub fn occupied_slots_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.map
+            .storage
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, e)| e.as_mut().map(|(k, _v)| (index, k)))
+    }
+
     /// Returns a reference to the provided index of the underlying array
     ///
     /// Returns `Some(&T)` if the index is in-bounds and has an element
+    ///
+    /// This is the inverse of [`PetitSet::find`].
+    ///
+    /// # Panics
+    /// Panics if the provided index is not less than CAP.
+    #[doc(alias = "element_at")]
     pub fn get_at(&self, index: usize) -> Option<&T> {
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitSet of capacity {CAP}"
+        );
+
         self.map.get_at(index).map(|(k, _v)| k)
     }
 
     /// Returns a mutable reference to the provided index of the underlying array
     ///
     /// Returns `Some(&mut T)` if the index is in-bounds and has an element
+    ///
+    /// # Panics
+    /// Panics if the provided index is not less than CAP.
     pub fn get_at_mut(&mut self, index: usize) -> Option<&mut T> {
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitSet of capacity {CAP}"
+        );
+
         self.map.get_at_mut(index).map(|(k, _v)| k)
     }
 
+    /// Returns mutable references to the elements at two distinct indexes of the underlying array
+    ///
+    /// Returns `None` if `index_a == index_b`, or if either index is out of bounds or empty.
+    /// It is a logic error to mutate an element so that it becomes equal to any other element in the set.
+    pub fn get_at_pair_mut(&mut self, index_a: usize, index_b: usize) -> Option<(&mut T, &mut T)> {
+        if index_a == index_b || index_a >= CAP || index_b >= CAP {
+            return None;
+        }
+
+        let (lower, upper) = (index_a.min(index_b), index_a.max(index_b));
+        let (front, back) = self.map.storage.split_at_mut(upper);
+        let lower_element = front[lower].as_mut().map(|(k, _v)| k)?;
+        let upper_element = back[0].as_mut().map(|(k, _v)| k)?;
+
+        if index_a < index_b {
+            Some((lower_element, upper_element))
+        } else {
+            Some((upper_element, lower_element))
+        }
+    }
+
+    /// Returns a reference to the element in the first filled slot
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.map.first().map(|(k, _v)| k)
+    }
+
+    /// Returns a mutable reference to the element in the first filled slot
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.map.first_mut().map(|(k, _v)| k)
+    }
+
+    /// Returns a reference to the element in the last filled slot
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.map.last().map(|(k, _v)| k)
+    }
+
+    /// Returns a mutable reference to the element in the last filled slot
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        self.map.last_mut().map(|(k, _v)| k)
+    }
+
     /// Removes all elements from the set without allocation
     pub fn clear(&mut self) {
         self.map.clear()
     }
 
+    /// Moves all elements into a new [`PetitSet`] with a capacity of `NEW_CAP`
+    ///
+    /// If every element fits within the new capacity at its current index, indexes are preserved.
+    /// Otherwise, the elements are compacted to the front of the new set, preserving their order.
+    ///
+    /// Returns a [`CapacityError`] containing the unchanged set if it has more than `NEW_CAP` elements.
+    pub fn into_capacity<const NEW_CAP: usize>(
+        self,
+    ) -> Result<PetitSet<T, NEW_CAP>, CapacityError<Self>> {
+        match self.map.into_capacity() {
+            Ok(map) => Ok(PetitSet { map }),
+            Err(CapacityError(map)) => Err(CapacityError(PetitSet { map })),
+        }
+    }
+
+    /// Compacts all elements into a new [`PetitSet`] with a capacity of `NEW_CAP`
+    ///
+    /// Unlike [`PetitSet::into_capacity`], slot positions are never preserved:
+    /// the elements are always moved to the front of the new set, preserving their order.
+    /// Use [`PetitSet::len`] to pick the tightest capacity that will fit.
+    ///
+    /// Returns a [`CapacityError`] containing the first element that did not fit if the set has more than `NEW_CAP` elements.
+    /// The remaining elements are dropped.
+    pub fn shrink<const NEW_CAP: usize>(self) -> Result<PetitSet<T, NEW_CAP>, CapacityError<T>> {
+        match self.map.shrink() {
+            Ok(map) => Ok(PetitSet { map }),
+            Err(CapacityError((k, _v))) => Err(CapacityError(k)),
+        }
+    }
+
+    /// Removes and returns the element in the last filled slot
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.map.pop().map(|(k, _v)| k)
+    }
+
+    /// Removes and returns the element in the first filled slot
+    ///
+    /// Returns `None` if the set is empty.
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.map.pop_first().map(|(k, _v)| k)
+    }
+
+    /// Shifts all elements towards the front of the set, removing any gaps
+    ///
+    /// The relative order of the elements is preserved, and all trailing slots are left empty.
+    ///
+    /// Returns the number of elements in the set.
+    pub fn compact(&mut self) -> usize {
+        self.map.compact()
+    }
+
+    /// Sorts the elements of the set with a comparator function, compacting them to the front of the set
+    ///
+    /// This sort is stable, and does not allocate.
+    /// It uses an insertion sort, which is O(CAP^2) in the worst case but very fast for small sets.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = self.compact();
+        for i in 1..len {
+            let mut j = i;
+            while j > 0 {
+                let should_swap = match (&self.map.storage[j - 1], &self.map.storage[j]) {
+                    (Some((a, _)), Some((b, _))) => compare(a, b) == Ordering::Greater,
+                    _ => false,
+                };
+                if !should_swap {
+                    break;
+                }
+                self.map.storage.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    /// Removes all elements from the set, returning them as an iterator
+    ///
+    /// Elements are yielded in slot order.
+    /// The set is left empty when the iterator is dropped, even if it was not fully consumed.
+    pub fn drain(&mut self) -> PetitSetDrain<'_, T, CAP> {
+        PetitSetDrain {
+            drain: self.map.drain(),
+        }
+    }
+
+    /// Removes and yields each element for which `pred` returns `true`
+    ///
+    /// Elements are visited in slot order; non-matching elements are left in their slots.
+    /// Only the elements that have already been yielded are removed:
+    /// dropping the iterator early leaves the remaining elements untouched.
+    pub fn extract_if<F>(&mut self, pred: F) -> PetitSetExtractIf<'_, T, F, CAP>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        PetitSetExtractIf {
+            set: self,
+            pred,
+            cursor: 0,
+        }
+    }
+
+    /// Moves all elements for which `pred` returns `true` into a new [`PetitSet`]
+    ///
+    /// Each moved element keeps its slot index in the returned set;
+    /// elements that do not match are left in place in `self`.
+    pub fn split_off<F>(&mut self, mut pred: F) -> PetitSet<T, CAP>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut split = PetitSet::new();
+        for index in 0..CAP {
+            if matches!(self.get_at(index), Some(element) if pred(element)) {
+                split.map.replace_slot(index, self.map.take_at(index));
+            }
+        }
+        split
+    }
+
     /// Removes the element at the provided index
     ///
     /// Returns true if an element was found
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
+    /// Panics if the provided index is not less than CAP.
     pub fn remove_at(&mut self, index: usize) -> bool {
         self.take_at(index).is_some()
     }
 
+    /// Removes the elements at each of the provided indexes, leaving all other elements in place
+    ///
+    /// Indexes that are already empty, or are not less than CAP, are ignored.
+    ///
+    /// Returns the number of elements that were removed.
+    pub fn remove_indices(&mut self, indices: impl IntoIterator<Item = usize>) -> usize {
+        let mut n_removed = 0;
+        for index in indices {
+            if index < CAP && self.remove_at(index) {
+                n_removed += 1;
+            }
+        }
+        n_removed
+    }
+
     /// Removes the element at the provided index
     ///
     /// Returns `Some(T)` if an element was found at that index, or `None` if no element was there.
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
+    /// Panics if the provided index is not less than CAP.
     #[must_use = "Use remove_at if the value is not needed."]
     pub fn take_at(&mut self, index: usize) -> Option<T> {
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitSet of capacity {CAP}"
+        );
+
         self.map.take_at(index).map(|(k, _v)| k)
     }
 
+    /// Removes the element at the provided index, moving the last element of the set into the vacated slot
+    ///
+    /// This is the order-breaking alternative to [`PetitSet::take_at`]:
+    /// rather than leaving a gap, the last element is moved, changing its index.
+    /// If the elements of the set are packed into its first slots, they remain so.
+    ///
+    /// Returns `Some(T)` if an element was found at that index, or `None` if no element was there.
+    ///
+    /// # Panics
+    /// Panics if the provided index is not less than CAP.
+    pub fn swap_remove_at(&mut self, index: usize) -> Option<T> {
+        let removed = self.take_at(index)?;
+
+        if let Some(last_index) = self.map.last_filled_index() {
+            if last_index > index {
+                let last = self.map.take_at(last_index);
+                self.map.replace_slot(index, last);
+            }
+        }
+
+        Some(removed)
+    }
+
     /// Swaps the element in `index_a` with the element in `index_b`
     ///
     /// # Panics
     ///
-    /// Panics if either index is greater than CAP.
+    /// Panics if either index is not less than CAP.
     pub fn swap_at(&mut self, index_a: usize, index_b: usize) {
+        assert!(
+            index_a < CAP,
+            "index {index_a} out of bounds for PetitSet of capacity {CAP}"
+        );
+        assert!(
+            index_b < CAP,
+            "index {index_b} out of bounds for PetitSet of capacity {CAP}"
+        );
+
         self.map.swap_at(index_a, index_b);
     }
 
@@ -156,18 +551,55 @@ impl<T: Eq, const CAP: usize> Extend<T> for PetitSet<T, CAP> {
     }
 }
 
+impl<'a, T: Eq + Copy, const CAP: usize> Extend<&'a T> for PetitSet<T, CAP> {
+    /// Inserts copies of multiple new elements to the set. Duplicate elements are discarded.
+    ///
+    /// # Panics
+    /// Panics if the set would overflow due to the insertion of non-duplicate items
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
 impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
     /// Returns the index for the provided element, if it exists in the set
-    pub fn find(&self, element: &T) -> Option<usize> {
+    ///
+    /// This is the inverse of [`PetitSet::get_at`].
+    #[doc(alias = "position_of", alias = "index_of")]
+    pub fn find<Q>(&self, element: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         self.map.find(element)
     }
 
     /// Is the provided element in the set?
     #[must_use]
-    pub fn contains(&self, element: &T) -> bool {
+    pub fn contains<Q>(&self, element: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         self.find(element).is_some()
     }
 
+    /// Are all of the provided elements in the set?
+    ///
+    /// Returns `false` as soon as an element that is not in the set is found.
+    #[must_use]
+    pub fn contains_all(&self, elements: impl IntoIterator<Item = T>) -> bool {
+        elements.into_iter().all(|element| self.contains(&element))
+    }
+
+    /// Are any of the provided elements in the set?
+    ///
+    /// Returns `true` as soon as an element that is in the set is found.
+    #[must_use]
+    pub fn contains_any(&self, elements: impl IntoIterator<Item = T>) -> bool {
+        elements.into_iter().any(|element| self.contains(&element))
+    }
+
     /// Attempt to insert a new element to the set in the first available slot.
     ///
     /// Inserts the element if able, then returns the [`Result`] of that operation.
@@ -176,7 +608,7 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
         match self.map.try_insert(element, ()) {
             Ok(success) => match success {
                 SuccesfulMapInsertion::NovelKey(index) => {
-                    Ok(SuccesfulSetInsertion::NovelElenent(index))
+                    Ok(SuccesfulSetInsertion::NovelElement(index))
                 }
                 SuccesfulMapInsertion::ExtantKey(_val, index) => {
                     Ok(SuccesfulSetInsertion::ExtantElement(index))
@@ -198,6 +630,127 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
             .expect("Inserting this element would have overflowed the set!")
     }
 
+    /// Inserts an element into the next empty index of the set, without scanning for duplicates
+    ///
+    /// Returns the index at which the element was stored.
+    ///
+    /// This is a faster alternative to [`PetitSet::insert`] for elements that are already known to be unique.
+    /// In builds with `debug_assertions` enabled, uniqueness is still checked.
+    ///
+    /// # Panics
+    /// Panics if the set is full.
+    /// With `debug_assertions` enabled, also panics if an equal element is already present.
+    pub fn insert_unique_unchecked(&mut self, element: T) -> usize {
+        debug_assert!(
+            !self.contains(&element),
+            "Attempted to insert a duplicate element with insert_unique_unchecked"
+        );
+
+        self.insert_unchecked(element)
+            .expect("Inserting this element would have overflowed the set!")
+    }
+
+    /// Attempts to insert an element into the set, replacing any existing element that is equal to it.
+    ///
+    /// The replacement is stored in the same slot as the existing element.
+    /// Unlike [`PetitSet::try_insert`], which keeps the original element, this is useful when
+    /// elements that compare equal can still be distinguished.
+    ///
+    /// Returns `Ok(Some(T))` containing the replaced element if one was found,
+    /// or a [`CapacityError`] if the element was not present and the set is full.
+    pub fn try_replace(&mut self, mut element: T) -> Result<Option<T>, CapacityError<T>> {
+        if let Some(index) = self.find(&element) {
+            let existing = self.get_at_mut(index).unwrap();
+            swap(existing, &mut element);
+            Ok(Some(element))
+        } else {
+            self.try_insert(element)?;
+            Ok(None)
+        }
+    }
+
+    /// Inserts an element into the set, replacing any existing element that is equal to it.
+    ///
+    /// The replacement is stored in the same slot as the existing element.
+    ///
+    /// Returns `Some(T)` containing the replaced element if one was found.
+    ///
+    /// # Panics
+    /// Panics if the set is full and the element is not a duplicate
+    pub fn replace(&mut self, element: T) -> Option<T> {
+        self.try_replace(element)
+            .expect("Inserting this element would have overflowed the set!")
+    }
+
+    /// Attempts to get a reference to the element in the set that is equal to the provided element,
+    /// inserting the provided element if no such element exists.
+    ///
+    /// Returns a [`CapacityError`] if the element was not present and the set is full.
+    pub fn try_get_or_insert(&mut self, element: T) -> Result<&T, CapacityError<T>> {
+        let index = match self.try_insert(element)? {
+            SuccesfulSetInsertion::NovelElement(index) => index,
+            SuccesfulSetInsertion::ExtantElement(index) => index,
+        };
+
+        Ok(self.get_at(index).unwrap())
+    }
+
+    /// Gets a reference to the element in the set that is equal to the provided element,
+    /// inserting the provided element if no such element exists.
+    ///
+    /// # Panics
+    /// Panics if the set is full and the element is not a duplicate
+    pub fn get_or_insert(&mut self, element: T) -> &T {
+        self.try_get_or_insert(element)
+            .expect("Inserting this element would have overflowed the set!")
+    }
+
+    /// Attempts to get a reference to the element in the set that is equal to the provided element,
+    /// inserting the element created by `f` if no such element exists.
+    ///
+    /// `f` is only called if no matching element is found.
+    /// It is a logic error for `f` to create an element that is not equal to `element`.
+    ///
+    /// Returns a [`CapacityError`] if the element was not present and the set is full.
+    pub fn try_get_or_insert_with<Q, F>(
+        &mut self,
+        element: &Q,
+        f: F,
+    ) -> Result<&T, CapacityError<T>>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+        F: FnOnce() -> T,
+    {
+        let index = match self.find(element) {
+            Some(index) => index,
+            None => match self.try_insert(f())? {
+                SuccesfulSetInsertion::NovelElement(index) => index,
+                SuccesfulSetInsertion::ExtantElement(index) => index,
+            },
+        };
+
+        Ok(self.get_at(index).unwrap())
+    }
+
+    /// Gets a reference to the element in the set that is equal to the provided element,
+    /// inserting the element created by `f` if no such element exists.
+    ///
+    /// `f` is only called if no matching element is found.
+    /// It is a logic error for `f` to create an element that is not equal to `element`.
+    ///
+    /// # Panics
+    /// Panics if the set is full and the element is not a duplicate
+    pub fn get_or_insert_with<Q, F>(&mut self, element: &Q, f: F) -> &T
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+        F: FnOnce() -> T,
+    {
+        self.try_get_or_insert_with(element, f)
+            .expect("Inserting this element would have overflowed the set!")
+    }
+
     /// Insert a new element to the set at the provided index
     ///
     /// If a matching element already existed in the set, it will be moved to the supplied index.
@@ -206,9 +759,50 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
     /// Returns `Some(T)` of any element removed by this operation.
     ///
     /// # Panics
-    /// Panics if the provided index is larger than CAP.
+    /// Panics if the provided index is not less than CAP.
     pub fn insert_at(&mut self, element: T, index: usize) -> Option<T> {
-        self.map.insert_at(element, (), index).map(|(k, _v)| k)
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitSet of capacity {CAP}"
+        );
+
+        match self.map.insert_at(element, (), index) {
+            InsertAtOutcome::Displaced((k, _v)) => Some(k),
+            InsertAtOutcome::Placed | InsertAtOutcome::MovedExisting { .. } => None,
+        }
+    }
+
+    /// Attempt to insert a new element to the set at the provided index
+    ///
+    /// If a matching element already exists in the set, the set is left unchanged
+    /// and an [`AlreadyPresent`] error containing the element and the index of the matching element is returned.
+    ///
+    /// Otherwise, returns `Ok(Some(T))` of any element that previously occupied the slot at `index`,
+    /// or `Ok(None)` if that slot was empty.
+    ///
+    /// # Panics
+    /// Panics if the provided index is not less than CAP.
+    pub fn try_insert_at(
+        &mut self,
+        element: T,
+        index: usize,
+    ) -> Result<Option<T>, AlreadyPresent<T>> {
+        assert!(
+            index < CAP,
+            "index {index} out of bounds for PetitSet of capacity {CAP}"
+        );
+
+        if let Some(existing_index) = self.find(&element) {
+            return Err(AlreadyPresent {
+                element,
+                index: existing_index,
+            });
+        }
+
+        Ok(self
+            .map
+            .replace_slot(index, Some((element, ())))
+            .map(|(k, _v)| k))
     }
 
     /// Inserts multiple new elements to the set. Duplicate elements are discarded.
@@ -227,7 +821,11 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
     /// Removes the element from the set, if it exists
     ///
     /// Returns `Some(index)` if the element was found, or `None` if no matching element is found
-    pub fn remove(&mut self, element: &T) -> Option<usize> {
+    pub fn remove<Q>(&mut self, element: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         self.map.remove(element)
     }
 
@@ -235,14 +833,38 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
     /// both the value that compared equal and the index at which
     /// it was stored.
     #[must_use = "Use remove if the value is not needed."]
-    pub fn take(&mut self, element: &T) -> Option<(usize, T)> {
+    pub fn take<Q>(&mut self, element: &Q) -> Option<(usize, T)>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         self.map.take(element).map(|(i, v)| (i, v.0))
     }
 
+    /// Removes an element from the set, if it exists, moving the last element of the set into the vacated slot
+    ///
+    /// This is the order-breaking alternative to [`PetitSet::remove`]:
+    /// slots are not preserved, as the last element of the set changes index.
+    /// See [`PetitSet::swap_remove_at`] for more details.
+    ///
+    /// Returns the element that compared equal, or `None` if no matching element is found.
+    pub fn swap_remove<Q>(&mut self, element: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        let index = self.find(element)?;
+        self.swap_remove_at(index)
+    }
+
     /// Swaps the positions of `element_a` with the position of `element_b`
     ///
     /// Returns true if both elements were found and successfully swapped.
-    pub fn swap(&mut self, element_a: &T, element_b: &T) -> bool {
+    pub fn swap<Q>(&mut self, element_a: &Q, element_b: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
         self.map.swap(element_a, element_b)
     }
 
@@ -254,6 +876,7 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all elements e such that f(&e) returns false. The elements are visited in order.
+    /// Retained elements are never moved to a new slot.
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&T) -> bool,
@@ -261,6 +884,35 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
         self.map.retain(|e, ()| f(e));
     }
 
+    /// Applies `f` to every element of the set, then removes any duplicates that this introduced
+    ///
+    /// Unlike [`PetitSet::iter_mut`], this cannot break the uniqueness of the set's elements.
+    /// When several elements become equal, the one in the lowest slot is kept and the others are removed.
+    /// Surviving elements are never moved to a new slot.
+    ///
+    /// The deduplication pass compares every pair of elements, and so is O(CAP^2).
+    pub fn mutate<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        for (element, ()) in self.map.storage.iter_mut().flatten() {
+            f(element);
+        }
+
+        for index in 0..CAP {
+            if self.get_at(index).is_none() {
+                continue;
+            }
+
+            for later_index in (index + 1)..CAP {
+                // The slot at `index` is filled, so this never matches an empty slot
+                if self.get_at(later_index) == self.get_at(index) {
+                    self.remove_at(later_index);
+                }
+            }
+        }
+    }
+
     /// Constructs a new [`PetitSet`] by consuming values from an iterator.
     ///
     /// The consumed values will be stored in order, with duplicate elements discarded.
@@ -294,6 +946,134 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
         }
     }
 
+    /// Constructs a new [`PetitSet`] by consuming values from an iterator,
+    /// counting how many duplicate elements were discarded.
+    ///
+    /// Returns the constructed set along with the number of duplicates skipped.
+    /// Overflow is reported in the same way as [`PetitSet::try_from_iter`]:
+    /// the error contains a [`PetitSet`] of all elements up to that point, and the element that could not be inserted.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let (set, duplicates) = PetitSet::<_, 5>::try_from_iter_counting([1, 2, 1, 4, 3, 1]).unwrap();
+    /// assert_eq!(set, PetitSet::<_, 5>::from_iter([1, 2, 4, 3]));
+    /// assert_eq!(duplicates, 2);
+    /// ```
+    pub fn try_from_iter_counting<I: IntoIterator<Item = T>>(
+        element_iter: I,
+    ) -> Result<(Self, usize), CapacityError<(Self, T)>> {
+        let mut set = Self::new();
+        let mut duplicates = 0;
+
+        for element in element_iter {
+            match set.try_insert(element) {
+                Ok(SuccesfulSetInsertion::NovelElement(_)) => (),
+                Ok(SuccesfulSetInsertion::ExtantElement(_)) => duplicates += 1,
+                Err(CapacityError(element)) => return Err(CapacityError((set, element))),
+            }
+        }
+
+        Ok((set, duplicates))
+    }
+
+    /// Constructs a new [`PetitSet`] by consuming values from an iterator,
+    /// counting how many duplicate elements were discarded.
+    ///
+    /// See [`PetitSet::try_from_iter_counting`] for more details.
+    ///
+    /// # Panics
+    /// Panics if the iterator produces more than `CAP` distinct elements.
+    pub fn from_iter_counting<I: IntoIterator<Item = T>>(element_iter: I) -> (Self, usize) {
+        match Self::try_from_iter_counting(element_iter) {
+            Ok(result) => result,
+            Err(_) => panic!("Inserting this element would have overflowed the set!"),
+        }
+    }
+
+    /// Construct a [`PetitSet`] from an array of `N` elements, discarding duplicates
+    ///
+    /// Elements are stored in order, packed into the first available slots.
+    /// As `N` must not exceed `CAP`, this can never overflow:
+    /// unlike [`PetitSet::try_from_iter`] or `TryFrom<[T; N]>`, there is no error to handle.
+    ///
+    /// # Compile errors
+    /// Fails to compile if `N` is greater than `CAP`.
+    ///
+    /// ```rust,compile_fail
+    /// use petitset::PetitSet;
+    ///
+    /// let set: PetitSet<u8, 2> = PetitSet::with_elements([1, 2, 3]);
+    /// ```
+    ///
+    /// # Example
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set: PetitSet<u8, 4> = PetitSet::with_elements([1, 2, 1]);
+    /// assert_eq!(set.len(), 2);
+    /// ```
+    pub fn with_elements<const N: usize>(elements: [T; N]) -> Self {
+        // Evaluating this constant fails to compile if the elements cannot fit
+        let () = AssertFits::<N, CAP>::OK;
+
+        let mut set = PetitSet::new();
+        for element in elements {
+            // N <= CAP, so this cannot overflow
+            set.insert(element);
+        }
+        set
+    }
+
+    /// Construct a [`PetitSet`] by calling `f` with each index in `0..CAP`, inserting each result in turn
+    ///
+    /// Like [`core::array::from_fn`], but duplicate elements are discarded,
+    /// so the resulting set may contain fewer than `CAP` elements.
+    /// Elements are stored in insertion order, packed into the first available slots.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set: PetitSet<u8, 4> = PetitSet::from_fn(|i| i as u8 / 2);
+    /// assert_eq!(set.len(), 2);
+    /// assert_eq!(set.get_at(0), Some(&0));
+    /// assert_eq!(set.get_at(1), Some(&1));
+    /// ```
+    pub fn from_fn<F: FnMut(usize) -> T>(mut f: F) -> Self {
+        let mut set = PetitSet::new();
+        for index in 0..CAP {
+            // There are never more than CAP elements to insert, so this cannot overflow
+            set.insert(f(index));
+        }
+        set
+    }
+
+    /// Construct a [`PetitSet`] from a fully-populated array, checking for duplicates.
+    ///
+    /// Each element is stored at the same index it had in the array.
+    ///
+    /// Returns a [`DuplicateError`] containing the first element that was already present.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petitset::DuplicateError;
+    /// use petitset::PetitSet;
+    ///
+    /// let set = PetitSet::try_from_array([1, 2, 3]);
+    /// assert_eq!(set, Ok(PetitSet::from_raw_array_unchecked([Some(1), Some(2), Some(3)])));
+    ///
+    /// let failed = PetitSet::try_from_array([1, 2, 1]);
+    /// assert_eq!(failed, Err(DuplicateError(1)));
+    /// ```
+    pub fn try_from_array(elements: [T; CAP]) -> Result<Self, DuplicateError<T>> {
+        match PetitMap::try_from_array(elements.map(|e| (e, ()))) {
+            Ok(map) => Ok(PetitSet { map }),
+            Err(DuplicateError((element, ()))) => Err(DuplicateError(element)),
+        }
+    }
+
     /// Construct a [`PetitSet`] directly from an array, without checking for duplicates.
     ///
     /// It is a logic error if any two non-`None` values in the array are equal, as elements are expected to be unique.
@@ -306,15 +1086,119 @@ impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
             map: PetitMap::from_raw_array_unchecked(values_for_map),
         }
     }
+
+    /// Returns an array of references to the contents of each slot
+    ///
+    /// This exposes the internal layout of the set: empty slots are `None`,
+    /// and each element is stored at its index.
+    ///
+    /// Elements are stored alongside a `()` value internally, so unlike [`PetitMap::as_array`],
+    /// this builds a new array rather than borrowing the underlying one.
+    pub fn as_array(&self) -> [Option<&T>; CAP] {
+        core::array::from_fn(|index| self.map.storage[index].as_ref().map(|(k, _v)| k))
+    }
+
+    /// Consumes the set, returning the underlying array of slots
+    ///
+    /// This is the inverse of [`PetitSet::from_raw_array_unchecked`], and exposes the internal layout of the set:
+    /// empty slots are `None`, and each element is stored at its index.
+    pub fn into_array(self) -> [Option<T>; CAP] {
+        self.map.into_array().map(|slot| slot.map(|(k, _v)| k))
+    }
+}
+
+impl<T: Ord, const CAP: usize> PetitSet<T, CAP> {
+    /// Sorts the elements of the set, compacting them to the front of the set
+    ///
+    /// After sorting, iteration will yield the elements in ascending order.
+    /// See [`PetitSet::sort_by`] for more details.
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp);
+    }
+}
+
+impl<T, const CAP: usize> Index<usize> for PetitSet<T, CAP> {
+    type Output = T;
+
+    /// Returns a reference to the element stored at the provided index of the underlying array.
+    ///
+    /// Use [`PetitSet::get_at`] to handle empty slots without panicking.
+    ///
+    /// # Panics
+    /// Panics if the provided index is not less than CAP, or if the slot at that index is empty.
+    fn index(&self, index: usize) -> &T {
+        self.get_at(index)
+            .unwrap_or_else(|| panic!("slot {index} of PetitSet is empty"))
+    }
+}
+
+impl<T, const CAP: usize> IndexMut<usize> for PetitSet<T, CAP> {
+    /// Returns a mutable reference to the element stored at the provided index of the underlying array.
+    ///
+    /// It is a logic error to modify the element so that it becomes equal to another element in the set.
+    /// Use [`PetitSet::get_at_mut`] to handle empty slots without panicking.
+    ///
+    /// # Panics
+    /// Panics if the provided index is not less than CAP, or if the slot at that index is empty.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_at_mut(index)
+            .unwrap_or_else(|| panic!("slot {index} of PetitSet is empty"))
+    }
 }
 
 impl<T: Eq, const CAP: usize> FromIterator<T> for PetitSet<T, CAP> {
     /// Panics if the iterator contains more than `CAP` distinct elements.
+    /// Use [`PetitSet::try_from_iter`] or [`TryCollectPetit::try_collect_set`](crate::TryCollectPetit::try_collect_set)
+    /// when the length of the iterator is not trusted.
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         PetitSet::try_from_iter(iter).unwrap()
     }
 }
 
+impl<'a, T: Eq + Copy, const CAP: usize> FromIterator<&'a T> for PetitSet<T, CAP> {
+    /// Constructs a new [`PetitSet`] from copies of the referenced elements.
+    ///
+    /// Panics if the iterator contains more than `CAP` distinct elements.
+    fn from_iter<I: IntoIterator<Item = &'a T>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl<T: Eq + Clone, const CAP: usize> TryFrom<&[T]> for PetitSet<T, CAP> {
+    type Error = CapacityError<(Self, T)>;
+
+    /// Constructs a new [`PetitSet`] by cloning the elements of a slice.
+    ///
+    /// See [`PetitSet::try_from_iter`] for more details.
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        PetitSet::try_from_iter(slice.iter().cloned())
+    }
+}
+
+impl<T: Eq, const N: usize, const CAP: usize> TryFrom<[T; N]> for PetitSet<T, CAP> {
+    type Error = CapacityError<(Self, T)>;
+
+    /// Constructs a new [`PetitSet`] from an array of any length, discarding duplicates.
+    ///
+    /// Unlike [`PetitSet::try_from_array`], elements are packed into the first available slots.
+    /// See [`PetitSet::try_from_iter`] for more details.
+    fn try_from(array: [T; N]) -> Result<Self, Self::Error> {
+        PetitSet::try_from_iter(array)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq, const CAP: usize> TryFrom<Vec<T>> for PetitSet<T, CAP> {
+    type Error = CapacityError<(Self, T)>;
+
+    /// Constructs a new [`PetitSet`] by moving the elements out of a [`Vec`], discarding duplicates.
+    ///
+    /// See [`PetitSet::try_from_iter`] for more details.
+    fn try_from(vec: Vec<T>) -> Result<Self, Self::Error> {
+        PetitSet::try_from_iter(vec)
+    }
+}
+
 impl<T: Eq, const CAP: usize> IntoIterator for PetitSet<T, CAP> {
     type Item = T;
     type IntoIter = PetitSetIter<T, CAP>;
@@ -326,6 +1210,39 @@ impl<T: Eq, const CAP: usize> IntoIterator for PetitSet<T, CAP> {
     }
 }
 
+impl<'a, T, const CAP: usize> IntoIterator for &'a PetitSet<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = FilterMap<Iter<'a, Option<(T, ())>>, fn(&'a Option<(T, ())>) -> Option<&'a T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.map
+            .storage
+            .iter()
+            .filter_map(|e| e.as_ref().map(|(k, _v)| k))
+    }
+}
+
+impl<'a, T, const CAP: usize> IntoIterator for &'a mut PetitSet<T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter =
+        FilterMap<IterMut<'a, Option<(T, ())>>, fn(&'a mut Option<(T, ())>) -> Option<&'a mut T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.map
+            .storage
+            .iter_mut()
+            .filter_map(|e| e.as_mut().map(|(k, _v)| k))
+    }
+}
+
+/// Checks at compile time that `N` elements fit within a capacity of `CAP`
+struct AssertFits<const N: usize, const CAP: usize>;
+
+impl<const N: usize, const CAP: usize> AssertFits<N, CAP> {
+    const OK: () = assert!(
+        N <= CAP,
+        "more elements were provided than the PetitSet can hold"
+    );
+}
+
 /// An [`Iterator`] struct for [`PetitSet`]
 #[derive(Clone, Debug)]
 pub struct PetitSetIter<T: Eq, const CAP: usize> {
@@ -336,7 +1253,8 @@ pub struct PetitSetIter<T: Eq, const CAP: usize> {
 impl<T: Eq, const CAP: usize> PetitSetIter<T, CAP> {
     /// Converts this iterator into the underlying [`PetitSet`]
     ///
-    /// Simpler and more direct than using `.collect()`
+    /// Simpler and more direct than using `.collect()`.
+    /// Only the elements that have not yet been yielded remain, each in its original slot.
     #[must_use]
     pub fn into_set(self) -> PetitSet<T, CAP> {
         self.set
@@ -357,6 +1275,83 @@ impl<T: Eq, const CAP: usize> Iterator for PetitSetIter<T, CAP> {
             None
         }
     }
+
+    /// Elements are removed as they are yielded, so the remaining length is exact
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.set.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: Eq, const CAP: usize> ExactSizeIterator for PetitSetIter<T, CAP> {}
+
+impl<T: Eq, const CAP: usize> FusedIterator for PetitSetIter<T, CAP> {}
+
+/// A draining [`Iterator`] struct for [`PetitSet`], created by [`PetitSet::drain`]
+#[derive(Debug)]
+pub struct PetitSetDrain<'a, T, const CAP: usize> {
+    drain: PetitMapDrain<'a, T, (), CAP>,
+}
+
+impl<'a, T, const CAP: usize> Iterator for PetitSetDrain<'a, T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next().map(|(k, _v)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'a, T, const CAP: usize> ExactSizeIterator for PetitSetDrain<'a, T, CAP> {}
+
+impl<'a, T, const CAP: usize> FusedIterator for PetitSetDrain<'a, T, CAP> {}
+
+/// An [`Iterator`] struct that removes matching elements from a [`PetitSet`], created by [`PetitSet::extract_if`]
+pub struct PetitSetExtractIf<'a, T, F, const CAP: usize> {
+    set: &'a mut PetitSet<T, CAP>,
+    pred: F,
+    cursor: usize,
+}
+
+impl<'a, T: Debug, F, const CAP: usize> Debug for PetitSetExtractIf<'a, T, F, CAP> {
+    /// The predicate is omitted, as closures do not implement [`Debug`]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PetitSetExtractIf")
+            .field("set", &self.set)
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T, F, const CAP: usize> Iterator for PetitSetExtractIf<'a, T, F, CAP>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cursor < CAP {
+            let index = self.cursor;
+            self.cursor += 1;
+
+            if matches!(self.set.get_at(index), Some(element) if (self.pred)(element)) {
+                return self.set.take_at(index);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(CAP - self.cursor))
+    }
+}
+
+impl<'a, T, F, const CAP: usize> FusedIterator for PetitSetExtractIf<'a, T, F, CAP> where
+    F: FnMut(&T) -> bool
+{
 }
 
 impl<T: Eq, const CAP: usize, const OTHER_CAP: usize> PartialEq<PetitSet<T, OTHER_CAP>>
@@ -395,7 +1390,83 @@ impl<T: Eq, const CAP: usize, const OTHER_CAP: usize> PartialEq<PetitSet<T, OTHE
 
 impl<T: Eq, const CAP: usize> Eq for PetitSet<T, CAP> {}
 
+impl<T: Debug, const CAP: usize> Debug for PetitSet<T, CAP> {
+    /// Formats the set as `{a, b, c}`, skipping empty slots
+    ///
+    /// With alternate formatting (`{:#?}`), each element is instead listed under the index of its slot,
+    /// as `{0: a, 3: b}`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_map().entries(self.iter_indexed()).finish()
+        } else {
+            f.debug_set().entries(self.iter()).finish()
+        }
+    }
+}
+
+impl<T: Display, const CAP: usize> Display for PetitSet<T, CAP> {
+    /// Formats the set as `{a, b, c}`, skipping empty slots
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, element) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{element}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<T: Hash, const CAP: usize> Hash for PetitSet<T, CAP> {
+    /// Hashes the elements of the set
+    ///
+    /// Like [`PartialEq`], this is order-independent: equal sets always hash equally.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_unordered(self.iter(), state);
+    }
+}
+
+impl<T: Ord, const CAP: usize> PartialOrd for PetitSet<T, CAP> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord, const CAP: usize> Ord for PetitSet<T, CAP> {
+    /// Compares the two sets lexicographically, as if their elements were sorted
+    ///
+    /// Like [`PartialEq`], this is order-independent.
+    /// Both sets are sorted (by reference, without allocation) during each comparison,
+    /// so this is O(CAP log CAP).
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (self_refs, self_len) = self.sorted_refs();
+        let (other_refs, other_len) = other.sorted_refs();
+
+        self_refs[..self_len].cmp(&other_refs[..other_len])
+    }
+}
+
+impl<T: Ord, const CAP: usize> PetitSet<T, CAP> {
+    /// Returns references to the elements of the set in sorted order, packed into the front of an array
+    ///
+    /// The number of references is also returned.
+    fn sorted_refs(&self) -> ([Option<&T>; CAP], usize) {
+        let mut refs = [None; CAP];
+        let mut len = 0;
+        for element in self.iter() {
+            refs[len] = Some(element);
+            len += 1;
+        }
+        refs[..len].sort_unstable();
+        (refs, len)
+    }
+}
+
 impl<T: Eq, const CAP: usize> Default for PetitSetIter<T, CAP> {
+    /// Creates an iterator over an empty [`PetitSet`], which yields nothing
+    ///
+    /// [`PetitSetIter::into_set`] can be used to recover the (empty) set.
     fn default() -> Self {
         Self {
             set: PetitSet::default(),
@@ -408,7 +1479,7 @@ impl<T: Eq, const CAP: usize> Default for PetitSetIter<T, CAP> {
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SuccesfulSetInsertion {
     /// This is a new element: it is stored at the provided index
-    NovelElenent(usize),
+    NovelElement(usize),
     /// This element was already in the set: it is stored at the provided index
     ExtantElement(usize),
 }
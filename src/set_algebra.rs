@@ -1,5 +1,10 @@
-//! Algebraic manipulations of `PetitSets`
+//! Algebraic manipulations of `PetitSets`, and of the keys of `PetitMaps`
+use crate::map::PetitMap;
 use crate::set::PetitSet;
+use crate::CapacityError;
+use core::ops::{BitAnd, Sub};
+#[cfg(feature = "set_algebra")]
+use core::ops::{BitOr, BitXor};
 
 #[cfg(feature = "set_algebra")]
 use crate::set::PetitSetIter;
@@ -141,6 +146,135 @@ impl<T: Eq + Clone, const CAP: usize> PetitSet<T, CAP> {
         iter
     }
 
+    /// Inserts clones of the values that are in `self` but not in `other` into `out`.
+    ///
+    /// Unlike `PetitSet::difference`, this does not require the `set_algebra` feature (and thus nightly Rust),
+    /// as the capacity of the result is chosen by the caller.
+    /// Any elements already in `out` are kept.
+    ///
+    /// Returns a [`CapacityError`] containing the first value that could not be inserted if `out` is full.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let mut set_a_minus_b: PetitSet<usize, 3> = PetitSet::new();
+    /// set_a.difference_into(&set_b, &mut set_a_minus_b).unwrap();
+    ///
+    /// assert_eq!(set_a_minus_b, PetitSet::<usize, 3>::from_iter([13]));
+    /// ```
+    pub fn difference_into<const OTHER_CAP: usize, const RESULT_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+        out: &mut PetitSet<T, RESULT_CAP>,
+    ) -> Result<(), CapacityError<T>> {
+        for s in self.iter() {
+            if !other.contains(s) {
+                out.try_insert(s.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inserts clones of the values that are not in both `self` and `other` into `out`.
+    ///
+    /// Unlike `PetitSet::symmetric_difference`, this does not require the `set_algebra` feature (and thus nightly Rust),
+    /// as the capacity of the result is chosen by the caller.
+    /// Any elements already in `out` are kept.
+    ///
+    /// Returns a [`CapacityError`] containing the first value that could not be inserted if `out` is full.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let mut set_a_sym_diff_b: PetitSet<usize, 4> = PetitSet::new();
+    /// set_a.symmetric_difference_into(&set_b, &mut set_a_sym_diff_b).unwrap();
+    ///
+    /// assert_eq!(set_a_sym_diff_b, PetitSet::<usize, 4>::from_iter([13, 15, 3, 4]));
+    /// ```
+    pub fn symmetric_difference_into<const OTHER_CAP: usize, const RESULT_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+        out: &mut PetitSet<T, RESULT_CAP>,
+    ) -> Result<(), CapacityError<T>> {
+        self.difference_into(other, out)?;
+        other.difference_into(self, out)
+    }
+
+    /// Inserts clones of the values that are in both `self` and `other` into `out`.
+    ///
+    /// Unlike `PetitSet::intersection`, this does not require the `set_algebra` feature (and thus nightly Rust),
+    /// as the capacity of the result is chosen by the caller.
+    /// Any elements already in `out` are kept.
+    ///
+    /// Returns a [`CapacityError`] containing the first value that could not be inserted if `out` is full.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let mut set_a_intersection_b: PetitSet<usize, 2> = PetitSet::new();
+    /// set_a.intersection_into(&set_b, &mut set_a_intersection_b).unwrap();
+    ///
+    /// assert_eq!(set_a_intersection_b, PetitSet::<usize, 2>::from_iter([7, 5]));
+    /// ```
+    pub fn intersection_into<const OTHER_CAP: usize, const RESULT_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+        out: &mut PetitSet<T, RESULT_CAP>,
+    ) -> Result<(), CapacityError<T>> {
+        for s in self.iter() {
+            if other.contains(s) {
+                out.try_insert(s.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inserts clones of the values that are in either `self` or `other` into `out`.
+    ///
+    /// Unlike `PetitSet::union`, this does not require the `set_algebra` feature (and thus nightly Rust),
+    /// as the capacity of the result is chosen by the caller.
+    /// Any elements already in `out` are kept.
+    ///
+    /// Returns a [`CapacityError`] containing the first value that could not be inserted if `out` is full.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::{CapacityError, PetitSet};
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let mut set_a_union_b: PetitSet<usize, 6> = PetitSet::new();
+    /// set_a.union_into(&set_b, &mut set_a_union_b).unwrap();
+    ///
+    /// assert_eq!(set_a_union_b, PetitSet::<usize, 6>::from_iter([7, 13, 5, 15, 3, 4]));
+    ///
+    /// let mut too_small: PetitSet<usize, 4> = PetitSet::new();
+    /// assert_eq!(set_a.union_into(&set_b, &mut too_small), Err(CapacityError(3)));
+    /// ```
+    pub fn union_into<const OTHER_CAP: usize, const RESULT_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+        out: &mut PetitSet<T, RESULT_CAP>,
+    ) -> Result<(), CapacityError<T>> {
+        out.try_extend(self.iter().cloned())?;
+        out.try_extend(other.iter().cloned())
+    }
+
     /// Do the sets contain any common elements?
     ///
     /// # Examples
@@ -224,6 +358,491 @@ impl<T: Eq + Clone, const CAP: usize> PetitSet<T, CAP> {
         }
         true
     }
+
+    /// Are all elements in `self` contained in `other`, with `other` containing at least one more element?
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([1, 2, 3]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([2, 3]);
+    ///
+    /// assert!(!set_a.is_proper_subset(&set_a));
+    ///
+    /// assert!(!set_a.is_proper_subset(&set_b));
+    /// assert!(set_b.is_proper_subset(&set_a));
+    /// ```
+    pub fn is_proper_subset<const OTHER_CAP: usize>(&self, other: &PetitSet<T, OTHER_CAP>) -> bool {
+        self.len() < other.len() && self.is_subset(other)
+    }
+
+    /// Are all elements in `other` contained in `self`, with `self` containing at least one more element?
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([1, 2, 3]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([2, 3]);
+    ///
+    /// assert!(!set_a.is_proper_superset(&set_a));
+    ///
+    /// assert!(set_a.is_proper_superset(&set_b));
+    /// assert!(!set_b.is_proper_superset(&set_a));
+    /// ```
+    pub fn is_proper_superset<const OTHER_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+    ) -> bool {
+        self.len() > other.len() && self.is_superset(other)
+    }
+}
+
+impl<T: Eq, const CAP: usize> PetitSet<T, CAP> {
+    #[cfg(feature = "set_algebra")]
+    /// Consumes `self`, returning an iterator of the values that are in `self` but not in `other`.
+    ///
+    /// Unlike [`PetitSet::difference`], this moves the values rather than cloning them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let set_a_minus_b: PetitSet<usize, 3> = PetitSet::from_iter([13]);
+    ///
+    /// let computed_set_a_minus_b = set_a.into_difference(&set_b).into_set();
+    ///
+    /// assert_eq!(set_a_minus_b, computed_set_a_minus_b);
+    /// ```
+    pub fn into_difference<const OTHER_CAP: usize>(
+        self,
+        other: &PetitSet<T, OTHER_CAP>,
+    ) -> PetitSetIter<T, CAP> {
+        let mut iter: PetitSetIter<T, CAP> = PetitSetIter::default();
+        for s in self {
+            if !other.contains(&s) {
+                iter.set.insert_unchecked(s);
+            }
+        }
+
+        iter
+    }
+
+    #[cfg(feature = "set_algebra")]
+    /// Consumes both sets, returning an iterator of the values that are not in both `self` and `other`.
+    ///
+    /// Unlike [`PetitSet::symmetric_difference`], this moves the values rather than cloning them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let set_a_sym_diff_b: PetitSet<usize, 8> = PetitSet::from_iter([13, 15, 3, 4]);
+    ///
+    /// let computed_set_a_sym_diff_b = set_a.into_symmetric_difference(set_b).into_set();
+    ///
+    /// assert_eq!(set_a_sym_diff_b, computed_set_a_sym_diff_b);
+    /// ```
+    pub fn into_symmetric_difference<const OTHER_CAP: usize>(
+        self,
+        mut other: PetitSet<T, OTHER_CAP>,
+    ) -> PetitSetIter<T, { CAP + OTHER_CAP }> {
+        let mut iter: PetitSetIter<T, { CAP + OTHER_CAP }> = PetitSetIter::default();
+        for s in self {
+            // Shared elements are removed from other, so only its unique elements remain
+            if other.remove(&s).is_none() {
+                iter.set.insert_unchecked(s);
+            }
+        }
+
+        for o in other {
+            iter.set.insert_unchecked(o);
+        }
+
+        iter
+    }
+
+    #[cfg(feature = "set_algebra")]
+    /// Consumes `self`, returning an iterator of the values that are in both `self` and `other`.
+    ///
+    /// Unlike [`PetitSet::intersection`], this moves the values rather than cloning them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let set_a_intersection_b: PetitSet<usize, 5> = PetitSet::from_iter([7, 5]);
+    ///
+    /// let computed_set_a_intersection_b = set_a.into_intersection(&set_b).into_set();
+    ///
+    /// assert_eq!(set_a_intersection_b, computed_set_a_intersection_b);
+    /// ```
+    pub fn into_intersection<const OTHER_CAP: usize>(
+        self,
+        other: &PetitSet<T, OTHER_CAP>,
+    ) -> PetitSetIter<T, { max_of(CAP, OTHER_CAP) }> {
+        let mut iter: PetitSetIter<T, { max_of(CAP, OTHER_CAP) }> = PetitSetIter::default();
+        for s in self {
+            if other.contains(&s) {
+                iter.set.insert_unchecked(s);
+            }
+        }
+        iter
+    }
+
+    #[cfg(feature = "set_algebra")]
+    /// Consumes both sets, returning an iterator of the values that are in either `self` or `other`.
+    ///
+    /// Unlike [`PetitSet::union`], this moves the values rather than cloning them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// let set_a_union_b: PetitSet<usize, 8> = PetitSet::from_iter([7, 13, 5, 15, 3, 4]);
+    ///
+    /// let computed_set_a_union_b = set_a.into_union(set_b).into_set();
+    ///
+    /// assert_eq!(set_a_union_b, computed_set_a_union_b);
+    /// ```
+    pub fn into_union<const OTHER_CAP: usize>(
+        self,
+        other: PetitSet<T, OTHER_CAP>,
+    ) -> PetitSetIter<T, { CAP + OTHER_CAP }> {
+        let mut iter: PetitSetIter<T, { CAP + OTHER_CAP }> = PetitSetIter::default();
+        for s in self {
+            iter.set.insert_unchecked(s);
+        }
+
+        for o in other {
+            // We are not guaranteed uniqueness by construction here
+            iter.set.insert(o);
+        }
+
+        iter
+    }
+
+    /// Removes every value that is also in `other` from `self`, in place.
+    ///
+    /// Remaining values are never moved to a new slot.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let mut set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// set_a.difference_update(&set_b);
+    /// assert_eq!(set_a, PetitSet::<usize, 1>::from_iter([13]));
+    /// ```
+    pub fn difference_update<const OTHER_CAP: usize>(&mut self, other: &PetitSet<T, OTHER_CAP>) {
+        self.retain(|s| !other.contains(s));
+    }
+
+    /// Removes every value that is not also in `other` from `self`, in place.
+    ///
+    /// Remaining values are never moved to a new slot.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let mut set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// set_a.intersection_update(&set_b);
+    /// assert_eq!(set_a, PetitSet::<usize, 2>::from_iter([7, 5]));
+    /// ```
+    pub fn intersection_update<const OTHER_CAP: usize>(&mut self, other: &PetitSet<T, OTHER_CAP>) {
+        self.retain(|s| other.contains(s));
+    }
+
+    /// Updates `self` in place to contain the values that are in exactly one of `self` and `other`.
+    ///
+    /// Values in both sets are removed from `self`, then the values only in `other` are moved into `self`.
+    /// As `other` is consumed, this does not require `T: Clone`.
+    ///
+    /// Returns a [`CapacityError`] containing the first value that could not be inserted if `self` is full.
+    /// Any values from `other` after that one are dropped.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let mut set_a: PetitSet<usize, 4> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 3> = PetitSet::from_iter([7, 3, 5]);
+    ///
+    /// set_a.symmetric_difference_update(set_b).unwrap();
+    /// assert_eq!(set_a, PetitSet::<usize, 2>::from_iter([13, 3]));
+    /// ```
+    pub fn symmetric_difference_update<const OTHER_CAP: usize>(
+        &mut self,
+        mut other: PetitSet<T, OTHER_CAP>,
+    ) -> Result<(), CapacityError<T>> {
+        // Remove the shared values from both sets, leaving only the values unique to `other` behind
+        other.retain(|o| self.remove(o).is_none());
+        self.try_extend(other)
+    }
+
+    /// Returns the number of values that are in both `self` and `other`.
+    ///
+    /// Unlike `PetitSet::intersection`, this does not build a new set,
+    /// and so does not require `T: Clone` or the `set_algebra` feature.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// assert_eq!(set_a.intersection_len(&set_b), 2);
+    /// assert_eq!(set_b.intersection_len(&set_a), 2);
+    /// ```
+    pub fn intersection_len<const OTHER_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+    ) -> usize {
+        // Scanning the smaller set minimizes the number of comparisons
+        if self.len() <= other.len() {
+            self.iter().filter(|s| other.contains(*s)).count()
+        } else {
+            other.iter().filter(|o| self.contains(*o)).count()
+        }
+    }
+
+    /// Returns the Jaccard index of the two sets: the size of their intersection divided by the size of their union.
+    ///
+    /// This is `1.0` for equal sets, and `0.0` for disjoint sets.
+    /// By convention, two empty sets are considered equal, and so return `1.0`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// assert_eq!(set_a.jaccard_similarity(&set_b), 2.0 / 6.0);
+    /// ```
+    pub fn jaccard_similarity<const OTHER_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+    ) -> f64 {
+        let intersection_len = self.intersection_len(other);
+        let union_len = self.len() + other.len() - intersection_len;
+
+        if union_len == 0 {
+            1.0
+        } else {
+            intersection_len as f64 / union_len as f64
+        }
+    }
+
+    /// Returns the overlap coefficient of the two sets: the size of their intersection divided by the size of the smaller set.
+    ///
+    /// This is `1.0` whenever one set is a subset of the other, and `0.0` for disjoint sets.
+    /// As the empty set is a subset of every set, this returns `1.0` if either set is empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// assert_eq!(set_a.overlap_coefficient(&set_b), 2.0 / 3.0);
+    /// ```
+    pub fn overlap_coefficient<const OTHER_CAP: usize>(
+        &self,
+        other: &PetitSet<T, OTHER_CAP>,
+    ) -> f64 {
+        let smaller_len = self.len().min(other.len());
+
+        if smaller_len == 0 {
+            1.0
+        } else {
+            self.intersection_len(other) as f64 / smaller_len as f64
+        }
+    }
+}
+
+impl<K: Eq, V, const CAP: usize> PetitMap<K, V, CAP> {
+    /// Returns an iterator of the key-value pairs in `self` whose keys are also in `other`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitMap;
+    ///
+    /// let map_a: PetitMap<&str, u8, 3> = PetitMap::from_iter([("a", 1), ("b", 2), ("c", 3)]);
+    /// let map_b: PetitMap<&str, bool, 2> = PetitMap::from_iter([("c", true), ("a", false)]);
+    ///
+    /// let intersection: Vec<(&&str, &u8)> = map_a.intersection_keys(&map_b).collect();
+    /// assert_eq!(intersection, vec![(&"a", &1), (&"c", &3)]);
+    /// ```
+    pub fn intersection_keys<'a, W, const OTHER_CAP: usize>(
+        &'a self,
+        other: &'a PetitMap<K, W, OTHER_CAP>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> + 'a {
+        self.iter()
+            .filter(|(k, _v)| other.contains_key(k))
+            .map(|(k, v)| (k, v))
+    }
+
+    /// Returns an iterator of the key-value pairs in `self` whose keys are not in `other`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitMap;
+    ///
+    /// let map_a: PetitMap<&str, u8, 3> = PetitMap::from_iter([("a", 1), ("b", 2), ("c", 3)]);
+    /// let map_b: PetitMap<&str, bool, 2> = PetitMap::from_iter([("c", true), ("a", false)]);
+    ///
+    /// let difference: Vec<(&&str, &u8)> = map_a.difference_keys(&map_b).collect();
+    /// assert_eq!(difference, vec![(&"b", &2)]);
+    /// ```
+    pub fn difference_keys<'a, W, const OTHER_CAP: usize>(
+        &'a self,
+        other: &'a PetitMap<K, W, OTHER_CAP>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> + 'a {
+        self.iter()
+            .filter(|(k, _v)| !other.contains_key(k))
+            .map(|(k, v)| (k, v))
+    }
+
+    /// Do the maps contain any common keys?
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitMap;
+    ///
+    /// let map_a: PetitMap<&str, u8, 3> = PetitMap::from_iter([("a", 1), ("b", 2), ("c", 3)]);
+    /// let map_b: PetitMap<&str, bool, 2> = PetitMap::from_iter([("c", true), ("a", false)]);
+    /// let map_c: PetitMap<&str, bool, 2> = PetitMap::from_iter([("d", true)]);
+    ///
+    /// assert!(!map_a.is_disjoint(&map_b));
+    /// assert!(map_a.is_disjoint(&map_c));
+    /// ```
+    pub fn is_disjoint<W, const OTHER_CAP: usize>(
+        &self,
+        other: &PetitMap<K, W, OTHER_CAP>,
+    ) -> bool {
+        !self.keys().any(|k| other.contains_key(k))
+    }
+}
+
+impl<T: Eq + Clone, const CAP: usize, const OTHER_CAP: usize> BitAnd<&PetitSet<T, OTHER_CAP>>
+    for &PetitSet<T, CAP>
+{
+    type Output = PetitSet<T, CAP>;
+
+    /// Returns the intersection of `self` and `other` as a new [`PetitSet`], with the same capacity as `self`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// assert_eq!(&set_a & &set_b, PetitSet::<usize, 3>::from_iter([7, 5]));
+    /// ```
+    fn bitand(self, other: &PetitSet<T, OTHER_CAP>) -> PetitSet<T, CAP> {
+        let mut out = PetitSet::new();
+        // The intersection is a subset of `self`, so it always fits
+        self.intersection_into(other, &mut out).unwrap();
+        out
+    }
+}
+
+impl<T: Eq + Clone, const CAP: usize, const OTHER_CAP: usize> Sub<&PetitSet<T, OTHER_CAP>>
+    for &PetitSet<T, CAP>
+{
+    type Output = PetitSet<T, CAP>;
+
+    /// Returns the difference of `self` and `other` as a new [`PetitSet`], with the same capacity as `self`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// assert_eq!(&set_a - &set_b, PetitSet::<usize, 3>::from_iter([13]));
+    /// ```
+    fn sub(self, other: &PetitSet<T, OTHER_CAP>) -> PetitSet<T, CAP> {
+        let mut out = PetitSet::new();
+        // The difference is a subset of `self`, so it always fits
+        self.difference_into(other, &mut out).unwrap();
+        out
+    }
+}
+
+#[cfg(feature = "set_algebra")]
+impl<T: Eq + Clone, const CAP: usize, const OTHER_CAP: usize> BitOr<&PetitSet<T, OTHER_CAP>>
+    for &PetitSet<T, CAP>
+where
+    PetitSet<T, { CAP + OTHER_CAP }>: Sized,
+{
+    type Output = PetitSet<T, { CAP + OTHER_CAP }>;
+
+    /// Returns the union of `self` and `other` as a new [`PetitSet`], with enough capacity for both.
+    ///
+    /// On stable Rust, use [`PetitSet::union_into`] instead.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// assert_eq!(&set_a | &set_b, PetitSet::<usize, 8>::from_iter([7, 13, 5, 15, 3, 4]));
+    /// ```
+    fn bitor(self, other: &PetitSet<T, OTHER_CAP>) -> PetitSet<T, { CAP + OTHER_CAP }> {
+        self.union(other).into_set()
+    }
+}
+
+#[cfg(feature = "set_algebra")]
+impl<T: Eq + Clone, const CAP: usize, const OTHER_CAP: usize> BitXor<&PetitSet<T, OTHER_CAP>>
+    for &PetitSet<T, CAP>
+where
+    PetitSet<T, { CAP + OTHER_CAP }>: Sized,
+{
+    type Output = PetitSet<T, { CAP + OTHER_CAP }>;
+
+    /// Returns the symmetric difference of `self` and `other` as a new [`PetitSet`], with enough capacity for both.
+    ///
+    /// On stable Rust, use [`PetitSet::symmetric_difference_into`] instead.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use petitset::PetitSet;
+    ///
+    /// let set_a: PetitSet<usize, 3> = PetitSet::from_iter([7, 13, 5]);
+    /// let set_b: PetitSet<usize, 5> = PetitSet::from_iter([15, 7, 3, 4, 5]);
+    ///
+    /// assert_eq!(&set_a ^ &set_b, PetitSet::<usize, 8>::from_iter([13, 15, 3, 4]));
+    /// ```
+    fn bitxor(self, other: &PetitSet<T, OTHER_CAP>) -> PetitSet<T, { CAP + OTHER_CAP }> {
+        self.symmetric_difference(other).into_set()
+    }
 }
 
 /// Trivial const replacement for `std::comp::Ord::max`
@@ -0,0 +1,22 @@
+#![cfg(feature = "arrayvec_compat")]
+
+use arrayvec::ArrayVec;
+use petitset::PetitSet;
+
+#[test]
+fn from_array_vec_dedups() {
+    let array_vec: ArrayVec<u8, 4> = ArrayVec::from([1, 2, 1, 3]);
+    let set: PetitSet<u8, 4> = array_vec.into();
+
+    assert_eq!(set.len(), 3);
+    assert_eq!(set, PetitSet::<u8, 4>::from_iter([1, 2, 3]));
+}
+
+#[test]
+fn into_array_vec_compacts() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    set.remove(&2);
+    let array_vec: ArrayVec<u8, 4> = set.into();
+
+    assert_eq!(array_vec.as_slice(), &[1, 3]);
+}
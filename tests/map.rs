@@ -1,4 +1,6 @@
-use petitset::PetitMap;
+use petitset::{
+    petitmap, CapacityError, Entry, FailedMapInsertion, InsertAtOutcome, PetitMap, PetitMapIter,
+};
 
 #[test]
 fn lookup() {
@@ -66,3 +68,806 @@ fn equality_ignores_order() {
 
     assert_eq!(map_1, map_2);
 }
+
+#[test]
+fn hash_ignores_order() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn calculate_hash<T: Hash>(t: &T) -> u64 {
+        let mut s = DefaultHasher::new();
+        t.hash(&mut s);
+        s.finish()
+    }
+
+    let mut map_1: PetitMap<i32, i32, 2> = PetitMap::default();
+    map_1.insert(1, 1);
+    map_1.insert(2, 2);
+
+    let mut map_2: PetitMap<i32, i32, 2> = PetitMap::default();
+    map_2.insert(2, 2);
+    map_2.insert(1, 1);
+
+    let mut map_3: PetitMap<i32, i32, 2> = PetitMap::default();
+    map_3.insert(1, 2);
+    map_3.insert(2, 1);
+
+    assert_eq!(calculate_hash(&map_1), calculate_hash(&map_2));
+    // Hashes are sensitive to which value belongs to which key
+    assert!(calculate_hash(&map_1) != calculate_hash(&map_3));
+}
+
+#[test]
+fn hash_keys_only() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn calculate_key_hash(map: &PetitMap<i32, i32, 3>) -> u64 {
+        let mut s = DefaultHasher::new();
+        map.hash_keys_only(&mut s);
+        s.finish()
+    }
+
+    fn calculate_hash<T: Hash>(t: &T) -> u64 {
+        let mut s = DefaultHasher::new();
+        t.hash(&mut s);
+        s.finish()
+    }
+
+    let map_1: PetitMap<i32, i32, 3> = PetitMap::from_iter([(1, 10), (2, 20)]);
+    let map_2: PetitMap<i32, i32, 3> = PetitMap::from_iter([(2, 1), (1, 2)]);
+    let map_3: PetitMap<i32, i32, 3> = PetitMap::from_iter([(1, 10), (3, 20)]);
+
+    // Values and order are ignored
+    assert_eq!(calculate_key_hash(&map_1), calculate_key_hash(&map_2));
+    assert!(calculate_key_hash(&map_1) != calculate_key_hash(&map_3));
+    assert_eq!(
+        calculate_key_hash(&map_1),
+        calculate_hash(&map_1.keys_set())
+    );
+
+    // The default hash still considers values
+    assert!(calculate_hash(&map_1) != calculate_hash(&map_2));
+}
+
+#[test]
+fn retain() {
+    let mut map: PetitMap<i32, i32, 8> = PetitMap::default();
+    map.extend((0..8).map(|i| (i, i * 10)));
+
+    map.retain(|_k, _v| true);
+    assert_eq!(map.len(), 8);
+
+    map.retain(|_k, v| *v % 20 == 0);
+    assert_eq!(map.len(), 4);
+    assert!(map.contains_key(&0));
+    assert!(!map.contains_key(&1));
+    assert!(map.contains_key(&6));
+    assert!(!map.contains_key(&7));
+
+    map.retain(|_k, _v| false);
+    assert!(map.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "index 4 out of bounds for PetitMap of capacity 4")]
+fn get_at_cap_panics() {
+    let map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.get_at(4);
+}
+
+#[test]
+#[should_panic(expected = "index 2 out of bounds for PetitMap of capacity 2")]
+fn insert_at_cap_panics() {
+    let mut map: PetitMap<i32, i32, 2> = PetitMap::default();
+    map.insert_at(1, 1, 2);
+}
+
+#[test]
+fn drain() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.insert(1, 11);
+    map.insert(2, 21);
+    map.insert(3, 31);
+
+    let drained: Vec<(i32, i32)> = map.drain().collect();
+    assert_eq!(drained, vec![(1, 11), (2, 21), (3, 31)]);
+    assert!(map.is_empty());
+
+    // Dropping a partially-consumed drain still empties the map
+    map.insert(1, 11);
+    map.insert(2, 21);
+    let mut drain = map.drain();
+    assert_eq!(drain.next(), Some((1, 11)));
+    drop(drain);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn borrowing_iteration() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.insert(1, 11);
+    map.insert(2, 21);
+
+    for (_key, value) in &mut map {
+        *value += 1;
+    }
+
+    let pairs: Vec<(&i32, &i32)> = (&map).into_iter().collect();
+    assert_eq!(pairs, vec![(&1, &12), (&2, &22)]);
+}
+
+#[test]
+fn index_by_key() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.insert(1, 11);
+    map.insert(2, 21);
+
+    assert_eq!(map[&2], 21);
+
+    map[&2] += 1;
+    assert_eq!(map[&2], 22);
+}
+
+#[test]
+#[should_panic(expected = "key not found in PetitMap")]
+fn index_missing_key_panics() {
+    let map: PetitMap<i32, i32, 4> = PetitMap::default();
+    let _value = map[&1];
+}
+
+#[test]
+fn consuming_keys_and_values() {
+    let mut map: PetitMap<String, String, 4> = PetitMap::default();
+    map.insert("a".to_string(), "one".to_string());
+    map.insert("b".to_string(), "two".to_string());
+
+    let keys: Vec<String> = map.clone().into_keys().collect();
+    assert_eq!(keys, vec!["a".to_string(), "b".to_string()]);
+
+    let values: Vec<String> = map.into_values().collect();
+    assert_eq!(values, vec!["one".to_string(), "two".to_string()]);
+}
+
+#[test]
+fn pop() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    assert_eq!(map.pop(), None);
+
+    map.insert(1, 11);
+    map.insert(2, 21);
+    map.insert(3, 31);
+
+    assert_eq!(map.pop(), Some((3, 31)));
+    assert_eq!(map.pop_first(), Some((1, 11)));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn first_and_last() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    assert_eq!(map.first(), None);
+
+    map.insert(1, 11);
+    map.insert(2, 21);
+    map.insert(3, 31);
+
+    assert_eq!(map.first(), Some((&1, &11)));
+    assert_eq!(map.last(), Some((&3, &31)));
+
+    *map.last_mut().unwrap().1 = 32;
+    assert_eq!(map.get(&3), Some(&32));
+}
+
+#[test]
+fn into_capacity() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.insert_at(1, 11, 3);
+
+    let larger: PetitMap<i32, i32, 8> = map.into_capacity().unwrap();
+    assert_eq!(larger.get_at(3), Some((&1, &11)));
+
+    let smaller: PetitMap<i32, i32, 1> = map.into_capacity().unwrap();
+    assert_eq!(smaller.get_at(0), Some((&1, &11)));
+}
+
+#[test]
+fn insert_if_absent() {
+    let mut map: PetitMap<i32, i32, 2> = PetitMap::default();
+
+    assert_eq!(map.try_insert_if_absent(1, 11), Ok(0));
+    assert_eq!(
+        map.try_insert_if_absent(1, 12),
+        Err(FailedMapInsertion::ExtantKey((1, 12), 0))
+    );
+    assert_eq!(map.get(&1), Some(&11));
+
+    assert_eq!(map.try_insert_if_absent(2, 21), Ok(1));
+    assert_eq!(
+        map.try_insert_if_absent(3, 31),
+        Err(FailedMapInsertion::Overfull(CapacityError((3, 31))))
+    );
+}
+
+#[test]
+fn append() {
+    let mut map: PetitMap<i32, i32, 3> = PetitMap::default();
+    map.insert(1, 11);
+    map.insert(2, 21);
+
+    let mut other: PetitMap<i32, i32, 2> = PetitMap::default();
+    other.insert(2, 22);
+    other.insert(3, 31);
+
+    assert_eq!(map.append(other), Ok(()));
+    assert_eq!(map.get(&2), Some(&22));
+    assert_eq!(map.get(&3), Some(&31));
+
+    let mut overflow: PetitMap<i32, i32, 1> = PetitMap::default();
+    overflow.insert(4, 41);
+    assert_eq!(map.append(overflow), Err(CapacityError((4, 41))));
+}
+
+#[test]
+fn get_disjoint_mut() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.insert(1, 11);
+    map.insert(2, 21);
+    map.insert(3, 31);
+
+    let [a, b] = map.get_disjoint_mut([&3, &1]).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(map.get(&1), Some(&31));
+    assert_eq!(map.get(&3), Some(&11));
+
+    assert!(map.get_disjoint_mut([&1, &4]).is_none());
+    assert!(map.get_disjoint_mut([&1, &2, &1]).is_none());
+}
+
+#[test]
+fn display() {
+    let mut map: PetitMap<&str, i32, 4> = PetitMap::default();
+    assert_eq!(map.to_string(), "{}");
+
+    map.insert("a", 1);
+    map.insert("b", 2);
+    map.insert("c", 3);
+    map.remove(&"b");
+    assert_eq!(map.to_string(), "{a: 1, c: 3}");
+}
+
+#[test]
+fn debug_skips_empty_slots() {
+    let mut map: PetitMap<u8, &str, 4> = PetitMap::default();
+    assert_eq!(format!("{map:?}"), "{}");
+
+    map.insert(1, "one");
+    map.insert(2, "two");
+    map.insert(3, "three");
+    map.remove(&2);
+    assert_eq!(format!("{map:?}"), r#"{1: "one", 3: "three"}"#);
+    assert_eq!(
+        format!("{map:#?}"),
+        "{\n    0: (\n        1,\n        \"one\",\n    ),\n    2: (\n        3,\n        \"three\",\n    ),\n}"
+    );
+}
+
+#[test]
+fn equality_is_symmetric() {
+    let mut map_1: PetitMap<i32, i32, 2> = PetitMap::default();
+    map_1.insert(1, 1);
+
+    let mut map_2: PetitMap<i32, i32, 4> = PetitMap::default();
+    map_2.insert(1, 1);
+    map_2.insert(2, 2);
+
+    assert_ne!(map_1, map_2);
+    assert_ne!(map_2, map_1);
+
+    map_2.remove(&2);
+    assert_eq!(map_1, map_2);
+    assert_eq!(map_2, map_1);
+}
+
+#[test]
+fn try_from_slice() {
+    let pairs = [(1, 11), (2, 21), (1, 12)];
+
+    let map = PetitMap::<i32, i32, 2>::try_from(&pairs[..]).unwrap();
+    assert_eq!(map.get(&1), Some(&12));
+    assert_eq!(map.get(&2), Some(&21));
+
+    assert!(PetitMap::<i32, i32, 1>::try_from(&pairs[..]).is_err());
+}
+
+#[test]
+fn extend_from_references() {
+    let mut other: PetitMap<i32, i32, 2> = PetitMap::default();
+    other.insert(1, 12);
+    other.insert(2, 21);
+
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.insert(1, 11);
+    map.extend(&other);
+
+    assert_eq!(map.get(&1), Some(&12));
+    assert_eq!(map.get(&2), Some(&21));
+}
+
+#[test]
+fn get_key_value_mut() {
+    let mut map: PetitMap<i32, i32, 4> = PetitMap::default();
+    map.insert(1, 11);
+
+    let (key, value) = map.get_key_value_mut(&1).unwrap();
+    *value += *key;
+    assert_eq!(map.get(&1), Some(&12));
+
+    assert!(map.get_key_value_mut(&2).is_none());
+}
+
+#[test]
+fn borrowed_key_lookup() {
+    let mut map: PetitMap<String, i32, 4> = PetitMap::default();
+    map.insert("a".to_string(), 1);
+    map.insert("b".to_string(), 2);
+
+    assert!(map.contains_key("a"));
+    assert_eq!(map.get("b"), Some(&2));
+    *map.get_mut("a").unwrap() += 10;
+    assert_eq!(map["a"], 11);
+    assert_eq!(map.remove("b"), Some(1));
+    assert_eq!(map.get("b"), None);
+}
+
+#[test]
+fn capacity_const() {
+    const CAP: usize = PetitMap::<u8, u8, 3>::CAPACITY;
+
+    assert_eq!(CAP, 3);
+    assert_eq!(PetitMap::<u8, u8, 3>::new().capacity(), CAP);
+}
+
+#[test]
+fn iter_mut() {
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::from_iter([(1, 10), (2, 20), (3, 30)]);
+    for (k, v) in map.iter_mut() {
+        if k % 2 == 1 {
+            *v += 1;
+        }
+    }
+
+    assert_eq!(map.get(&1), Some(&11));
+    assert_eq!(map.get(&2), Some(&20));
+    assert_eq!(map.get(&3), Some(&31));
+}
+
+#[test]
+fn map_values() {
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::from_iter([(1, 10), (2, 20), (3, 30)]);
+    map.remove(&2);
+    let mapped: PetitMap<u8, String, 4> = map.map_values(|v| v.to_string());
+
+    assert_eq!(mapped.len(), 2);
+    assert_eq!(mapped.get_at(0), Some((&1, &"10".to_string())));
+    assert_eq!(mapped.get_at(1), None);
+    assert_eq!(mapped.get_at(2), Some((&3, &"30".to_string())));
+}
+
+#[test]
+fn try_collect_map() {
+    use petitset::TryCollectPetit;
+
+    let map = [(1, 10), (2, 20), (1, 11)]
+        .into_iter()
+        .try_collect_map::<_, _, 2>();
+    assert_eq!(
+        map,
+        Ok(PetitMap::<u8, u8, 2>::from_iter([(1, 11), (2, 20)]))
+    );
+
+    let failed = (0..3u8).map(|i| (i, i)).try_collect_map::<_, _, 2>();
+    assert_eq!(failed.unwrap_err().0 .1, (2, 2));
+}
+
+#[test]
+fn cached_len_stays_consistent() {
+    fn check<const CAP: usize>(map: &PetitMap<u8, u8, CAP>) {
+        assert_eq!(map.len(), map.iter().count());
+        assert_eq!(map.is_empty(), map.iter().count() == 0);
+        assert_eq!(map.is_full(), map.iter().count() == CAP);
+    }
+
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::default();
+    check(&map);
+
+    map.insert(1, 10);
+    map.insert(2, 20);
+    // Overwriting an existing key does not change the length
+    map.insert(2, 21);
+    check(&map);
+
+    map.insert_at(3, 30, 0);
+    check(&map);
+    map.insert_at(4, 40, 3);
+    check(&map);
+
+    map.remove(&1);
+    let _ = map.take_at(3);
+    map.remove_at(3);
+    check(&map);
+
+    map.retain(|k, _v| *k != 2);
+    check(&map);
+
+    map.extend([(5, 50), (6, 60), (7, 70)]);
+    check(&map);
+
+    map.drain().next();
+    check(&map);
+
+    let raw = PetitMap::from_raw_array_unchecked([Some((1, 1)), None, Some((2, 2)), None]);
+    check(&raw);
+    let mut bigger: PetitMap<u8, u8, 8> = raw.into_capacity().unwrap();
+    check(&bigger);
+
+    bigger.clear();
+    check(&bigger);
+}
+
+#[test]
+fn iter_indexed() {
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::from_iter([(1, 10), (2, 20), (3, 30)]);
+    map.remove(&1);
+
+    let indexed: Vec<(usize, &u8, &u8)> = map.iter_indexed().collect();
+    assert_eq!(indexed, vec![(1, &2, &20), (2, &3, &30)]);
+}
+
+#[test]
+fn copy() {
+    let map: PetitMap<u8, u8, 4> = PetitMap::from_iter([(1, 10), (2, 20)]);
+    let copied = map;
+    assert_eq!(map, copied);
+}
+
+#[test]
+fn insert_at_outcome() {
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::default();
+
+    assert_eq!(map.insert_at(1, 10, 0), InsertAtOutcome::Placed);
+    assert_eq!(map.insert_at(2, 20, 0), InsertAtOutcome::Displaced((1, 10)));
+    assert_eq!(map.insert_at(3, 30, 1), InsertAtOutcome::Placed);
+
+    // Inserting an existing key swaps it into place, keeping its value
+    assert_eq!(
+        map.insert_at(2, 21, 1),
+        InsertAtOutcome::MovedExisting { from: 0 }
+    );
+    assert_eq!(map.get_at(0), Some((&3, &30)));
+    assert_eq!(map.get_at(1), Some((&2, &20)));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn shrink() {
+    let mut map: PetitMap<u8, u8, 8> = PetitMap::default();
+    map.insert_at(1, 10, 3);
+    map.insert_at(2, 20, 6);
+
+    let shrunk: PetitMap<u8, u8, 2> = map.shrink().unwrap();
+    assert_eq!(shrunk.get_at(0), Some((&1, &10)));
+    assert_eq!(shrunk.get_at(1), Some((&2, &20)));
+
+    // Slots are compacted even when they would have fit
+    let same: PetitMap<u8, u8, 8> = map.shrink().unwrap();
+    assert_eq!(same.get_at(0), Some((&1, &10)));
+
+    let failed: Result<PetitMap<u8, u8, 1>, _> = map.shrink();
+    assert_eq!(failed, Err(CapacityError((2, 20))));
+}
+
+#[test]
+fn retain_keys_and_values() {
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::from_iter([(1, 40), (2, 30), (3, 20), (4, 10)]);
+
+    map.retain_keys(|k| k % 2 == 0);
+    assert_eq!(map.get_at(1), Some((&2, &30)));
+    assert_eq!(map.get_at(3), Some((&4, &10)));
+    assert_eq!(map.len(), 2);
+
+    map.retain_values(|v| *v > 15);
+    assert_eq!(map.get_at(1), Some((&2, &30)));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn array_round_trip() {
+    let raw = [Some((1, 10)), None, Some((2, 20))];
+    let map: PetitMap<u8, u8, 3> = PetitMap::from_raw_array_unchecked(raw);
+
+    assert_eq!(map.as_array(), &raw);
+    assert_eq!(map.into_array(), raw);
+}
+
+#[test]
+fn predicate_helpers() {
+    let map: PetitMap<u8, u8, 4> = PetitMap::from_iter([(1, 10), (2, 25), (3, 30)]);
+
+    assert_eq!(map.count_where(|_k, v| v % 10 == 0), 2);
+    assert!(map.any(|k, v| *k == 2 && *v == 25));
+    assert!(!map.any(|k, _v| *k > 3));
+    assert!(map.all(|k, v| v > k));
+    assert!(!map.all(|_k, v| *v < 30));
+}
+
+#[test]
+fn try_from_array_of_any_length() {
+    let map = PetitMap::<u8, u8, 2>::try_from([(1, 10), (1, 11), (2, 20)]).unwrap();
+    assert_eq!(map.get(&1), Some(&11));
+    assert_eq!(map.len(), 2);
+
+    assert!(PetitMap::<u8, u8, 1>::try_from([(1, 10), (2, 20)]).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn try_from_vec() {
+    let map = PetitMap::<u8, u8, 2>::try_from(vec![(1, 10), (2, 20)]).unwrap();
+    assert_eq!(map.get_at(1), Some((&2, &20)));
+
+    assert!(PetitMap::<u8, u8, 1>::try_from(vec![(1, 10), (2, 20)]).is_err());
+}
+
+#[test]
+fn entry_api() {
+    let mut map: PetitMap<String, usize, 2> = PetitMap::new();
+
+    // The closure can use the key without cloning it
+    assert_eq!(
+        *map.entry("four".to_string())
+            .or_insert_with_key(|k| k.len()),
+        4
+    );
+    assert_eq!(*map.entry("four".to_string()).or_insert_with_key(|_k| 0), 4);
+
+    map.entry("four".to_string()).and_modify(|v| *v += 1);
+    assert_eq!(map.get("four"), Some(&5));
+
+    *map.entry("zero".to_string()).or_default() += 1;
+    assert_eq!(map.get("zero"), Some(&1));
+
+    match map.entry("four".to_string()) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.index(), 0);
+            assert_eq!(entry.insert(40), 5);
+            assert_eq!(entry.remove_entry(), ("four".to_string(), 40));
+        }
+        Entry::Vacant(_) => panic!("Key should have been present"),
+    }
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn vacant_entry_in_full_map() {
+    let mut map: PetitMap<u8, u8, 1> = PetitMap::from_iter([(1, 10)]);
+
+    match map.entry(2) {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.try_insert(20), Err(CapacityError((2, 20))));
+        }
+        Entry::Occupied(_) => panic!("Key should have been absent"),
+    }
+}
+
+#[test]
+#[should_panic]
+fn entry_or_insert_in_full_map_panics() {
+    let mut map: PetitMap<u8, u8, 1> = PetitMap::from_iter([(1, 10)]);
+    map.entry(2).or_insert(20);
+}
+
+#[test]
+fn remaining_capacity() {
+    let mut map: PetitMap<u8, u8, 3> = PetitMap::from_iter([(1, 10)]);
+    assert_eq!(map.remaining_capacity(), 2);
+    assert!(map.can_fit(2));
+    assert!(!map.can_fit(3));
+
+    map.remove(&1);
+    assert_eq!(map.remaining_capacity(), 3);
+}
+
+#[test]
+fn truncate() {
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::from_iter([(1, 10), (2, 20), (3, 30)]);
+    map.truncate(1);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get_at(0), Some((&1, &10)));
+
+    // Freed slots can be reused
+    map.insert(4, 40);
+    assert_eq!(map.get_at(1), Some((&4, &40)));
+}
+
+#[test]
+fn petitmap_macro() {
+    let map = petitmap! { 1 => "one", 2 => "two" };
+    assert_eq!(map.capacity(), 2);
+    assert_eq!(map.get_at(1), Some((&2, &"two")));
+
+    let overwritten = petitmap! { 1 => "one", 1 => "uno" };
+    assert_eq!(overwritten.capacity(), 2);
+    assert_eq!(overwritten.len(), 1);
+    assert_eq!(overwritten.get(&1), Some(&"uno"));
+}
+
+#[test]
+fn map_iter_default_is_empty() {
+    let mut iter: PetitMapIter<u8, u8, 4> = PetitMapIter::default();
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert!(iter.into_map().is_empty());
+}
+
+#[test]
+fn keys_and_values_sets() {
+    let mut map: PetitMap<u8, &str, 4> = PetitMap::default();
+    map.insert_at(1, "odd", 0);
+    map.insert_at(2, "even", 2);
+    map.insert_at(3, "odd", 3);
+
+    let keys = map.keys_set();
+    assert_eq!(keys.len(), 3);
+    // Keys keep their slots
+    assert_eq!(keys.get_at(1), None);
+    assert_eq!(keys.get_at(2), Some(&2));
+
+    let values = map.values_set();
+    assert_eq!(values.len(), 2);
+    assert_eq!(values.get_at(0), Some(&"odd"));
+    assert_eq!(values.get_at(1), Some(&"even"));
+}
+
+#[test]
+fn indices() {
+    let mut map: PetitMap<u8, u8, 4> = PetitMap::default();
+    map.insert_at(1, 10, 1);
+    map.insert_at(2, 20, 3);
+
+    let indices: Vec<usize> = map.indices().collect();
+    assert_eq!(indices, vec![1, 3]);
+}
+
+#[test]
+fn update() {
+    let mut map: PetitMap<&str, i32, 4> = PetitMap::default();
+    map.insert("a", 1);
+
+    assert!(map.update("a", |value| *value += 10));
+    assert_eq!(map.get("a"), Some(&11));
+
+    assert!(!map.update("b", |value| *value += 10));
+    assert_eq!(map.get("b"), None);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn get_or_insert_with() {
+    let mut map: PetitMap<u8, u32, 2> = PetitMap::default();
+
+    *map.get_or_insert_with(1, || 10) += 1;
+    assert_eq!(map.get(&1), Some(&11));
+
+    // The existing value is returned, and `make` is not called
+    *map.get_or_insert_with(1, || unreachable!()) += 1;
+    assert_eq!(map.get(&1), Some(&12));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn try_get_or_insert_with() {
+    let mut map: PetitMap<u8, u32, 1> = PetitMap::default();
+
+    assert_eq!(map.try_get_or_insert_with(1, || 10), Ok(&mut 10));
+    assert_eq!(
+        map.try_get_or_insert_with(1, || unreachable!()),
+        Ok(&mut 10)
+    );
+    assert_eq!(
+        map.try_get_or_insert_with(2, || unreachable!()),
+        Err(CapacityError(2))
+    );
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn get_or_insert_with_overflow() {
+    let mut map: PetitMap<u8, u32, 1> = PetitMap::default();
+    map.insert(1, 10);
+
+    map.get_or_insert_with(2, || 20);
+}
+
+#[test]
+fn as_ref_slice() {
+    fn count_empty<T>(slots: impl AsRef<[Option<T>]>) -> usize {
+        slots.as_ref().iter().filter(|slot| slot.is_none()).count()
+    }
+
+    let mut map: PetitMap<u8, &str, 4> = PetitMap::default();
+    map.insert(1, "one");
+    map.insert(2, "two");
+    map.insert(3, "three");
+    map.remove(&2);
+
+    let slots: &[Option<(u8, &str)>] = map.as_ref();
+    assert_eq!(slots, &[Some((1, "one")), None, Some((3, "three")), None]);
+    assert_eq!(count_empty(map), 2);
+}
+
+#[test]
+fn capacity_error_into_failed_insertion() {
+    let error: FailedMapInsertion<u8, u8> = CapacityError((1, 2)).into();
+    assert_eq!(error, FailedMapInsertion::Overfull(CapacityError((1, 2))));
+}
+
+#[test]
+fn contains_value() {
+    let mut map: PetitMap<&str, u8, 4> = PetitMap::default();
+    assert!(!map.contains_value(&1));
+
+    map.insert("a", 1);
+    map.insert("b", 2);
+    map.insert("c", 1);
+    assert!(map.contains_value(&1));
+    assert!(map.contains_value(&2));
+    assert!(!map.contains_value(&3));
+
+    map.remove(&"b");
+    assert!(!map.contains_value(&2));
+}
+
+#[test]
+fn find_by_value() {
+    let mut map: PetitMap<&str, u8, 4> = PetitMap::default();
+    map.insert("a", 1);
+    map.insert("b", 2);
+    map.insert("c", 1);
+
+    assert_eq!(map.find_by_value(&1), Some(&"a"));
+    assert_eq!(map.find_by_value(&2), Some(&"b"));
+    assert_eq!(map.find_by_value(&3), None);
+
+    map.remove(&"a");
+    assert_eq!(map.find_by_value(&1), Some(&"c"));
+}
+
+#[test]
+fn keys_for_value() {
+    let mut map: PetitMap<&str, u8, 4> = PetitMap::default();
+    map.insert("a", 1);
+    map.insert("b", 2);
+    map.insert("c", 1);
+
+    let keys = map.keys_for_value(&1);
+    let slots: Vec<(usize, &&str)> = keys.iter_indexed().collect();
+    assert_eq!(slots, vec![(0, &"a"), (2, &"c")]);
+
+    assert!(map.keys_for_value(&3).is_empty());
+}
+
+#[test]
+fn capacity_error_accessors() {
+    let error = PetitMap::<u8, u8, 1>::try_from_iter([(1, 10), (2, 20)]).unwrap_err();
+    assert_eq!(
+        error.partial(),
+        &PetitMap::<u8, u8, 1>::from_iter([(1, 10)])
+    );
+    assert_eq!(error.rejected(), &(2, 20));
+
+    let (partial, rejected) = error.into_parts();
+    assert_eq!(partial.len(), 1);
+    assert_eq!(rejected, (2, 20));
+}
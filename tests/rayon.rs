@@ -0,0 +1,33 @@
+#![cfg(feature = "rayon_compat")]
+
+use petitset::{PetitMap, PetitSet};
+use rayon::prelude::*;
+
+#[test]
+fn parallel_sum() {
+    let mut set: PetitSet<u32, 64> = PetitSet::default();
+    set.extend(0..64);
+    set.remove(&7);
+    set.remove(&42);
+
+    let sequential_sum: u32 = set.iter().sum();
+    let borrowed_parallel_sum: u32 = set.par_iter().sum();
+    let owned_parallel_sum: u32 = set.into_par_iter().sum();
+
+    assert_eq!(sequential_sum, borrowed_parallel_sum);
+    assert_eq!(sequential_sum, owned_parallel_sum);
+}
+
+#[test]
+fn parallel_map_sum() {
+    let mut map: PetitMap<u32, u32, 64> = PetitMap::default();
+    map.extend((0..64).map(|i| (i, i * 2)));
+    map.remove(&13);
+
+    let sequential_sum: u32 = map.values().sum();
+    let borrowed_parallel_sum: u32 = map.par_iter().map(|(_k, v)| v).sum();
+    let owned_parallel_sum: u32 = map.into_par_iter().map(|(_k, v)| v).sum();
+
+    assert_eq!(sequential_sum, borrowed_parallel_sum);
+    assert_eq!(sequential_sum, owned_parallel_sum);
+}
@@ -0,0 +1,176 @@
+#![cfg(feature = "bevy_reflect_compat")]
+
+use bevy_reflect::{ApplyError, FromReflect, PartialReflect, ReflectMut, ReflectRef, TypeRegistry};
+use petitset::{PetitMap, PetitSet};
+
+#[test]
+fn set_reflects_as_list() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    set.remove(&2);
+
+    let ReflectRef::List(list) = set.reflect_ref() else {
+        panic!("PetitSet should be reflected as a list");
+    };
+    // Empty slots are skipped
+    assert_eq!(list.len(), 2);
+    assert_eq!(list.get(1).unwrap().try_downcast_ref::<u8>(), Some(&3));
+}
+
+#[test]
+fn set_apply_and_push() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2]);
+    let patch: PetitSet<u8, 4> = PetitSet::from_iter([5, 6, 7]);
+    set.apply(&patch);
+    assert_eq!(set, patch);
+
+    let ReflectMut::List(list) = set.reflect_mut() else {
+        panic!("PetitSet should be reflected as a list");
+    };
+    // Duplicates are discarded
+    list.push(Box::new(5u8));
+    list.push(Box::new(8u8));
+    assert_eq!(set, PetitSet::<u8, 4>::from_iter([5, 6, 7, 8]));
+}
+
+#[test]
+fn set_apply_shorter_patch() {
+    let mut set: PetitSet<u32, 4> = PetitSet::from_iter([5, 6]);
+    set.apply(&PetitSet::<u32, 4>::from_iter([1]));
+
+    // Surplus elements are removed
+    assert_eq!(set, PetitSet::<u32, 4>::from_iter([1]));
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn set_apply_discards_duplicates() {
+    let mut set: PetitSet<u32, 4> = PetitSet::from_iter([5, 6]);
+    set.apply(&vec![1u32, 1]);
+
+    assert_eq!(set, PetitSet::<u32, 4>::from_iter([1]));
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn set_try_apply_errors() {
+    let mut set: PetitSet<u32, 1> = PetitSet::from_iter([5]);
+
+    // Too many distinct elements
+    assert!(matches!(
+        set.try_apply(&vec![1u32, 2]),
+        Err(ApplyError::DifferentSize {
+            from_size: 2,
+            to_size: 1
+        })
+    ));
+    // Wrong element type
+    assert!(matches!(
+        set.try_apply(&vec![1u8]),
+        Err(ApplyError::MismatchedTypes { .. })
+    ));
+    // Failed patches leave the set unchanged
+    assert_eq!(set, PetitSet::<u32, 1>::from_iter([5]));
+}
+
+#[test]
+fn set_reflect_partial_eq_is_order_independent() {
+    let set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    let reordered: PetitSet<u8, 8> = PetitSet::from_iter([3, 1, 2]);
+
+    assert_eq!(set.reflect_partial_eq(&reordered), Some(true));
+    assert_eq!(set.reflect_partial_eq(&vec![1u8, 2]), Some(false));
+}
+
+#[test]
+fn set_from_reflect() {
+    let set = PetitSet::<u8, 3>::from_reflect(&vec![1u8, 2, 1, 3]).unwrap();
+    assert_eq!(set, PetitSet::<u8, 3>::from_iter([1, 2, 3]));
+
+    // Too many distinct elements
+    assert!(PetitSet::<u8, 2>::from_reflect(&vec![1u8, 2, 3]).is_none());
+}
+
+#[test]
+fn map_reflects_as_map() {
+    let mut map: PetitMap<u8, u16, 4> = PetitMap::from_iter([(1, 10), (2, 20)]);
+
+    let ReflectRef::Map(reflected) = map.reflect_ref() else {
+        panic!("PetitMap should be reflected as a map");
+    };
+    assert_eq!(reflected.len(), 2);
+    assert_eq!(
+        reflected.get(&2u8).unwrap().try_downcast_ref::<u16>(),
+        Some(&20)
+    );
+
+    let patch: PetitMap<u8, u16, 4> = PetitMap::from_iter([(2, 25), (3, 30)]);
+    map.apply(&patch);
+    // Keys that are missing from the patch are removed
+    assert_eq!(map.get(&1), None);
+    assert_eq!(map.get(&2), Some(&25));
+    assert_eq!(map.get(&3), Some(&30));
+}
+
+#[test]
+fn map_apply_replaces_stale_keys() {
+    // The stale keys must be removed before the new ones are inserted, or this would overflow
+    let mut map: PetitMap<u8, u16, 2> = PetitMap::from_iter([(1, 10), (2, 20)]);
+    let patch: PetitMap<u8, u16, 2> = PetitMap::from_iter([(3, 30), (4, 40)]);
+
+    map.try_apply(&patch).unwrap();
+    assert_eq!(map, patch);
+}
+
+#[test]
+fn map_try_apply_overflow() {
+    let mut map: PetitMap<u8, u16, 1> = PetitMap::from_iter([(1, 10)]);
+    let patch: PetitMap<u8, u16, 2> = PetitMap::from_iter([(1, 15), (2, 20)]);
+
+    assert!(matches!(
+        map.try_apply(&patch),
+        Err(ApplyError::DifferentSize {
+            from_size: 2,
+            to_size: 1
+        })
+    ));
+}
+
+#[test]
+fn map_try_apply_errors_leave_map_unchanged() {
+    let mut map: PetitMap<u8, u16, 2> = PetitMap::from_iter([(1, 10), (2, 20)]);
+
+    // Too many keys
+    let patch: PetitMap<u8, u16, 3> = PetitMap::from_iter([(3, 30), (4, 40), (5, 50)]);
+    assert!(map.try_apply(&patch).is_err());
+    assert_eq!(map, PetitMap::<u8, u16, 2>::from_iter([(1, 10), (2, 20)]));
+
+    // A new value of the wrong type
+    let patch: PetitMap<u8, u8, 2> = PetitMap::from_iter([(3, 30)]);
+    assert!(matches!(
+        map.try_apply(&patch),
+        Err(ApplyError::MismatchedTypes { .. })
+    ));
+    assert_eq!(map, PetitMap::<u8, u16, 2>::from_iter([(1, 10), (2, 20)]));
+}
+
+#[test]
+fn map_clone_and_from_reflect() {
+    let map: PetitMap<u8, u16, 4> = PetitMap::from_iter([(1, 10), (2, 20)]);
+
+    let cloned = map.reflect_clone().unwrap();
+    assert_eq!(cloned.reflect_partial_eq(&map), Some(true));
+
+    let dynamic = map.to_dynamic();
+    let rebuilt = PetitMap::<u8, u16, 4>::from_reflect(&*dynamic).unwrap();
+    assert_eq!(rebuilt, map);
+}
+
+#[test]
+fn registration() {
+    let mut registry = TypeRegistry::default();
+    registry.register::<PetitSet<u8, 4>>();
+    registry.register::<PetitMap<u8, u16, 4>>();
+
+    assert!(registry.contains(core::any::TypeId::of::<PetitSet<u8, 4>>()));
+    assert!(registry.contains(core::any::TypeId::of::<u16>()));
+}
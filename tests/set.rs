@@ -1,7 +1,9 @@
 mod predicates;
 use predicates::is_sorted;
 
-use petitset::{CapacityError, PetitSet, SuccesfulSetInsertion};
+use petitset::{
+    petitset, AlreadyPresent, CapacityError, PetitSet, PetitSetIter, SuccesfulSetInsertion,
+};
 
 #[test]
 fn reject_duplicates() {
@@ -89,6 +91,17 @@ fn equality_ignores_order() {
     assert_eq!(set_1, set_2);
 }
 
+#[test]
+fn equality_ignores_capacity() {
+    let small: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    let large: PetitSet<u8, 8> = PetitSet::from_iter([3, 2, 1]);
+    assert_eq!(small, large);
+    assert_eq!(large, small);
+
+    let different: PetitSet<u8, 8> = PetitSet::from_iter([1, 2, 4]);
+    assert_ne!(small, different);
+}
+
 #[test]
 fn removal_returns_items() {
     let mut set: PetitSet<u8, 8> = PetitSet::default();
@@ -145,8 +158,1101 @@ fn hashable() {
 
     assert_eq!(calculate_hash(&set_1), calculate_hash(&set_1));
     assert_eq!(calculate_hash(&set_1), calculate_hash(&set_2));
-    // Hashes are sensitive to slot
-    assert!(calculate_hash(&set_1) != calculate_hash(&set_3));
+    // Hashes are independent of slot, just like equality
+    assert_eq!(set_1, set_3);
+    assert_eq!(calculate_hash(&set_1), calculate_hash(&set_3));
     // Hashes are sensitive to element value
     assert!(calculate_hash(&set_1) != calculate_hash(&set_4));
+
+    let set_5: PetitSet<u8, 8> = PetitSet::from_iter([1, 2, 3]);
+    let set_6: PetitSet<u8, 8> = PetitSet::from_iter([3, 1, 2]);
+    // Hashes are independent of insertion order
+    assert_eq!(calculate_hash(&set_5), calculate_hash(&set_6));
+}
+
+#[test]
+#[should_panic(expected = "index 4 out of bounds for PetitSet of capacity 4")]
+fn get_at_cap_panics() {
+    let set: PetitSet<u8, 4> = PetitSet::default();
+    set.get_at(4);
+}
+
+#[test]
+#[should_panic(expected = "index 2 out of bounds for PetitSet of capacity 2")]
+fn swap_at_cap_panics() {
+    let mut set: PetitSet<u8, 2> = PetitSet::default();
+    set.swap_at(0, 2);
+}
+
+#[test]
+fn mutable_iteration() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    set.extend(0..4);
+    set.remove(&1);
+
+    for element in set.iter_mut() {
+        *element *= 10;
+    }
+
+    assert_eq!(set.get_at(0), Some(&0));
+    assert_eq!(set.get_at(1), None);
+    assert_eq!(set.get_at(2), Some(&20));
+    assert_eq!(set.get_at(3), Some(&30));
+}
+
+#[test]
+fn drain() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    set.extend(0..8);
+    set.remove(&2);
+
+    let drained: Vec<u8> = set.drain().collect();
+    assert_eq!(drained, vec![0, 1, 3, 4, 5, 6, 7]);
+    assert!(set.is_empty());
+
+    // Dropping a partially-consumed drain still empties the set
+    set.extend(0..8);
+    let mut drain = set.drain();
+    assert_eq!(drain.next(), Some(0));
+    drop(drain);
+    assert!(set.is_empty());
+}
+
+#[test]
+fn borrowing_iteration() {
+    let mut set: PetitSet<u8, 4> = PetitSet::default();
+    set.extend(1..=3);
+
+    for element in &mut set {
+        *element += 1;
+    }
+
+    let mut sum = 0;
+    for element in &set {
+        sum += element;
+    }
+    assert_eq!(sum, 2 + 3 + 4);
+}
+
+#[test]
+fn compact() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    set.extend(0..8);
+    set.remove(&2);
+    set.remove(&3);
+    set.remove(&6);
+
+    let len = set.compact();
+    assert_eq!(len, 5);
+    assert!(is_sorted(&set));
+
+    assert_eq!(set.next_empty_index(0), Some(len));
+    assert_eq!(set.next_filled_index(len), None);
+    for index in 0..len {
+        assert_eq!(set.next_filled_index(index), Some(index));
+    }
+}
+
+#[test]
+fn sort() {
+    let mut empty_set: PetitSet<u8, 4> = PetitSet::default();
+    empty_set.sort();
+    assert!(empty_set.is_empty());
+
+    let mut single_set: PetitSet<u8, 4> = PetitSet::default();
+    single_set.insert_at(7, 2);
+    single_set.sort();
+    assert_eq!(single_set.get_at(0), Some(&7));
+
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    set.extend((0..8).rev());
+    set.remove(&5);
+    set.remove(&2);
+    assert!(!is_sorted(&set));
+
+    set.sort();
+    assert!(is_sorted(&set));
+    assert_eq!(set.len(), 6);
+    assert_eq!(set.next_empty_index(0), Some(6));
+
+    set.sort_by(|a, b| b.cmp(a));
+    assert_eq!(set.get_at(0), Some(&7));
+    assert_eq!(set.get_at(5), Some(&0));
+}
+
+#[test]
+fn ordering_ignores_order() {
+    use std::collections::BTreeSet;
+
+    let set_1: PetitSet<u8, 4> = PetitSet::from_iter([3, 1, 2]);
+    let set_2: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    let set_3: PetitSet<u8, 4> = PetitSet::from_iter([2, 1]);
+    let set_4: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 4]);
+
+    assert_eq!(set_1.cmp(&set_2), std::cmp::Ordering::Equal);
+    assert!(set_3 < set_1);
+    assert!(set_4 > set_1);
+
+    let mut btree_set = BTreeSet::new();
+    btree_set.insert(set_1);
+    btree_set.insert(set_2);
+    btree_set.insert(set_3);
+    assert_eq!(btree_set.len(), 2);
+}
+
+#[test]
+fn get_or_insert() {
+    let mut set: PetitSet<u8, 2> = PetitSet::default();
+
+    assert_eq!(*set.get_or_insert(1), 1);
+    assert_eq!(*set.get_or_insert(1), 1);
+    assert_eq!(set.len(), 1);
+
+    let mut calls = 0;
+    set.get_or_insert_with(&2, || {
+        calls += 1;
+        2
+    });
+    set.get_or_insert_with(&2, || {
+        calls += 1;
+        2
+    });
+    assert_eq!(calls, 1);
+    assert_eq!(set.len(), 2);
+
+    assert_eq!(set.try_get_or_insert(1), Ok(&1));
+    assert_eq!(set.try_get_or_insert(3), Err(CapacityError(3)));
+    assert_eq!(set.try_get_or_insert_with(&3, || 3), Err(CapacityError(3)));
+}
+
+#[test]
+fn pop() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    assert_eq!(set.pop(), None);
+    assert_eq!(set.pop_first(), None);
+
+    set.extend(0..4);
+    set.remove(&3);
+    set.remove(&0);
+
+    assert_eq!(set.pop(), Some(2));
+    assert_eq!(set.pop_first(), Some(1));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn first_and_last() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    assert_eq!(set.first(), None);
+    assert_eq!(set.last(), None);
+
+    set.insert_at(3, 2);
+    set.insert_at(5, 6);
+
+    assert_eq!(set.first(), Some(&3));
+    assert_eq!(set.last(), Some(&5));
+
+    *set.first_mut().unwrap() = 4;
+    *set.last_mut().unwrap() = 6;
+    assert_eq!(set.get_at(2), Some(&4));
+    assert_eq!(set.get_at(6), Some(&6));
+}
+
+#[test]
+fn retain_preserves_slots() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    set.extend(0..8);
+
+    set.retain(|e| e % 2 == 0);
+    assert_eq!(set.len(), 4);
+
+    for i in 0..8 {
+        if i % 2 == 0 {
+            assert_eq!(set.get_at(i as usize), Some(&i));
+        } else {
+            assert_eq!(set.get_at(i as usize), None);
+        }
+    }
+}
+
+#[test]
+fn exact_size_iteration() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    set.extend(0..5);
+    set.remove(&1);
+
+    let mut iter = set.into_iter();
+    assert_eq!(iter.len(), 4);
+    iter.next();
+    iter.next();
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    iter.next();
+    iter.next();
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.len(), 0);
+
+    let mut drain = set.drain();
+    assert_eq!(drain.len(), 4);
+    drain.next();
+    assert_eq!(drain.len(), 3);
+}
+
+#[test]
+fn into_capacity() {
+    let mut set: PetitSet<u8, 4> = PetitSet::default();
+    set.insert_at(1, 1);
+    set.insert_at(2, 2);
+
+    // Indexes are preserved when they fit
+    let larger: PetitSet<u8, 8> = set.into_capacity().unwrap();
+    assert_eq!(larger.get_at(1), Some(&1));
+    assert_eq!(larger.get_at(2), Some(&2));
+
+    // Otherwise, elements are compacted
+    let smaller: PetitSet<u8, 2> = set.into_capacity().unwrap();
+    assert_eq!(smaller.get_at(0), Some(&1));
+    assert_eq!(smaller.get_at(1), Some(&2));
+
+    // Failed conversions return the original set
+    let failed: Result<PetitSet<u8, 1>, _> = set.into_capacity();
+    assert_eq!(failed, Err(CapacityError(set)));
+}
+
+#[test]
+fn display() {
+    let mut set: PetitSet<u8, 4> = PetitSet::default();
+    assert_eq!(set.to_string(), "{}");
+
+    set.extend(1..=3);
+    set.remove(&2);
+    assert_eq!(set.to_string(), "{1, 3}");
+}
+
+#[test]
+fn debug_skips_empty_slots() {
+    let mut set: PetitSet<u8, 4> = PetitSet::default();
+    assert_eq!(format!("{set:?}"), "{}");
+
+    set.extend(1..=3);
+    set.remove(&2);
+    assert_eq!(format!("{set:?}"), "{1, 3}");
+    assert_eq!(format!("{set:#?}"), "{\n    0: 1,\n    2: 3,\n}");
+}
+
+#[test]
+fn try_from_slice() {
+    let elements = [1, 2, 1, 3];
+
+    let set = PetitSet::<u8, 3>::try_from(&elements[..]).unwrap();
+    assert_eq!(set, PetitSet::<u8, 3>::from_iter([1, 2, 3]));
+
+    let failed = PetitSet::<u8, 2>::try_from(&elements[..]);
+    assert_eq!(failed, Err(CapacityError((PetitSet::from_iter([1, 2]), 3))));
+}
+
+#[test]
+fn contains_all_and_any() {
+    let set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+
+    assert!(set.contains_all([1, 3]));
+    assert!(!set.contains_all([1, 4]));
+    assert!(set.contains_all([]));
+
+    assert!(set.contains_any([4, 3]));
+    assert!(!set.contains_any([4, 5]));
+    assert!(!set.contains_any([]));
+}
+
+#[test]
+fn extend_from_references() {
+    let other: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([3, 4]);
+    set.extend(other.iter());
+    assert_eq!(set, PetitSet::<u8, 4>::from_iter([1, 2, 3, 4]));
+}
+
+#[test]
+fn collect_from_references() {
+    let set: PetitSet<u8, 8> = PetitSet::from_iter(1..=6);
+
+    let evens: PetitSet<u8, 4> = set.iter().filter(|&&element| element % 2 == 0).collect();
+    assert_eq!(evens, PetitSet::<u8, 4>::from_iter([2, 4, 6]));
+}
+
+#[test]
+#[should_panic]
+fn collect_from_references_overflow() {
+    let set: PetitSet<u8, 8> = PetitSet::from_iter(1..=6);
+
+    let _: PetitSet<u8, 4> = set.iter().collect();
+}
+
+#[test]
+fn replace() {
+    #[derive(Debug, Clone, Copy)]
+    struct Tagged {
+        id: u8,
+        tag: char,
+    }
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Tagged {}
+
+    let mut set: PetitSet<Tagged, 2> = PetitSet::default();
+    assert_eq!(set.replace(Tagged { id: 1, tag: 'a' }), None);
+    set.insert(Tagged { id: 2, tag: 'a' });
+
+    let replaced = set.replace(Tagged { id: 1, tag: 'b' }).unwrap();
+    assert_eq!(replaced.tag, 'a');
+    assert_eq!(set.get_at(0).unwrap().tag, 'b');
+
+    let overfull = set.try_replace(Tagged { id: 3, tag: 'a' });
+    assert!(overfull.is_err());
+}
+
+#[test]
+fn borrowed_element_lookup() {
+    let mut set: PetitSet<String, 4> = PetitSet::default();
+    set.insert("a".to_string());
+    set.insert("b".to_string());
+
+    assert!(set.contains("a"));
+    assert_eq!(set.find("b"), Some(1));
+    assert_eq!(set.take("a"), Some((0, "a".to_string())));
+    assert!(!set.contains("a"));
+}
+
+#[test]
+fn extract_if() {
+    let mut set: PetitSet<u8, 6> = PetitSet::from_iter([1, 2, 3, 4, 5]);
+    let extracted: Vec<u8> = set.extract_if(|x| x % 2 == 0).collect();
+
+    assert_eq!(extracted, vec![2, 4]);
+    assert_eq!(set.len(), 3);
+    // Non-matching elements stay in their original slots
+    assert_eq!(set.get_at(0), Some(&1));
+    assert_eq!(set.get_at(1), None);
+    assert_eq!(set.get_at(2), Some(&3));
+    assert_eq!(set.get_at(4), Some(&5));
+}
+
+#[test]
+fn extract_if_dropped_early() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3, 4]);
+    let first = set.extract_if(|_| true).next();
+
+    assert_eq!(first, Some(1));
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&2) && set.contains(&3) && set.contains(&4));
+}
+
+#[test]
+fn capacity_const() {
+    const CAP: usize = PetitSet::<u8, 7>::CAPACITY;
+    let buffer = [0u8; CAP];
+
+    assert_eq!(buffer.len(), 7);
+    assert_eq!(PetitSet::<u8, 7>::new().capacity(), CAP);
+}
+
+#[test]
+fn new_with_non_copy_elements() {
+    let set: PetitSet<String, 16> = PetitSet::new();
+
+    assert!(set.is_empty());
+    assert_eq!(set.capacity(), 16);
+}
+
+#[test]
+fn try_from_iter_non_copy_overflow() {
+    let elements = ["a", "b", "a", "c"].map(String::from);
+    let result = PetitSet::<String, 2>::try_from_iter(elements);
+
+    let CapacityError((partial, overflow)) = result.unwrap_err();
+    assert_eq!(overflow, "c");
+    assert_eq!(partial.len(), 2);
+    assert!(partial.contains("a") && partial.contains("b"));
+}
+
+#[test]
+fn split_off() {
+    let mut set: PetitSet<u8, 5> = PetitSet::from_iter([1, 2, 3, 4]);
+    let evens = set.split_off(|x| x % 2 == 0);
+
+    assert_eq!(set, PetitSet::<u8, 5>::from_iter([1, 3]));
+    assert_eq!(evens, PetitSet::<u8, 5>::from_iter([2, 4]));
+    // Slots are preserved
+    assert_eq!(evens.get_at(1), Some(&2));
+    assert_eq!(evens.get_at(3), Some(&4));
+}
+
+#[test]
+fn split_off_all_or_none() {
+    let mut set: PetitSet<u8, 3> = PetitSet::from_iter([1, 2, 3]);
+
+    let none = set.split_off(|_| false);
+    assert!(none.is_empty());
+    assert_eq!(set.len(), 3);
+
+    let all = set.split_off(|_| true);
+    assert!(set.is_empty());
+    assert_eq!(all, PetitSet::<u8, 3>::from_iter([1, 2, 3]));
+}
+
+#[test]
+fn remove_indices() {
+    let mut set: PetitSet<u8, 5> = PetitSet::from_iter([1, 2, 3, 4, 5]);
+    set.remove_at(1);
+
+    // Index 1 is already empty and index 9 is out of range
+    let n_removed = set.remove_indices([0, 1, 3, 9]);
+
+    assert_eq!(n_removed, 2);
+    assert_eq!(set.get_at(2), Some(&3));
+    assert_eq!(set.get_at(4), Some(&5));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn try_collect_set() {
+    use petitset::TryCollectPetit;
+
+    let set = [1, 2, 1, 3].into_iter().try_collect_set::<3>();
+    assert_eq!(set, Ok(PetitSet::<u8, 3>::from_iter([1, 2, 3])));
+
+    let CapacityError((partial, overflow)) = (0..10u8).try_collect_set::<3>().unwrap_err();
+    assert_eq!(partial, PetitSet::<u8, 3>::from_iter([0, 1, 2]));
+    assert_eq!(overflow, 3);
+}
+
+#[test]
+fn insert_unique_unchecked() {
+    let mut set: PetitSet<u8, 3> = PetitSet::default();
+
+    assert_eq!(set.insert_unique_unchecked(1), 0);
+    assert_eq!(set.insert_unique_unchecked(2), 1);
+    assert_eq!(set, PetitSet::<u8, 3>::from_iter([1, 2]));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Attempted to insert a duplicate element")]
+fn insert_unique_unchecked_duplicate() {
+    let mut set: PetitSet<u8, 3> = PetitSet::default();
+    set.insert_unique_unchecked(1);
+    set.insert_unique_unchecked(1);
+}
+
+#[test]
+fn cached_len_stays_consistent() {
+    fn check<const CAP: usize>(set: &PetitSet<u8, CAP>) {
+        assert_eq!(set.len(), set.iter().count());
+        assert_eq!(set.is_empty(), set.iter().count() == 0);
+        assert_eq!(set.is_full(), set.iter().count() == CAP);
+    }
+
+    let mut set: PetitSet<u8, 6> = PetitSet::from_iter([1, 2, 3, 4, 5, 6]);
+    check(&set);
+
+    set.remove(&1);
+    set.remove_indices([1, 2]);
+    check(&set);
+
+    let odds = set.split_off(|x| x % 2 == 1);
+    check(&set);
+    check(&odds);
+
+    let _: Vec<u8> = set.extract_if(|x| *x == 4).collect();
+    check(&set);
+
+    set.insert_unchecked(9);
+    set.pop();
+    check(&set);
+}
+
+#[test]
+fn freed_slots_are_reused() {
+    let mut set: PetitSet<u8, 8> = PetitSet::from_iter(0..8);
+    set.remove_at(5);
+    set.remove_at(2);
+
+    assert_eq!(set.next_empty_index(0), Some(2));
+    assert_eq!(set.insert(100), SuccesfulSetInsertion::NovelElement(2));
+    assert_eq!(set.insert(101), SuccesfulSetInsertion::NovelElement(5));
+    assert_eq!(set.next_empty_index(0), None);
+
+    set.swap_at(0, 1);
+    set.remove_at(1);
+    set.swap_at(1, 7);
+    // The emptied slot moved to the end
+    assert_eq!(set.next_empty_index(0), Some(7));
+}
+
+#[test]
+fn sequential_insertion_fills_slots_in_order() {
+    let mut set: PetitSet<u16, 512> = PetitSet::default();
+    for i in 0..512 {
+        assert_eq!(
+            set.insert(i),
+            SuccesfulSetInsertion::NovelElement(i as usize)
+        );
+    }
+    assert!(set.is_full());
+}
+
+#[test]
+fn iter_indexed() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    set.remove(&2);
+
+    let indexed: Vec<(usize, &u8)> = set.iter_indexed().collect();
+    assert_eq!(indexed, vec![(0, &1), (2, &3)]);
+}
+
+#[test]
+fn copy() {
+    let set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2]);
+    let copied = set;
+    assert_eq!(set, copied);
+}
+
+#[test]
+fn try_insert_at() {
+    let mut set: PetitSet<u8, 4> = PetitSet::default();
+
+    assert_eq!(set.try_insert_at(1, 2), Ok(None));
+    assert_eq!(set.try_insert_at(2, 2), Ok(Some(1)));
+    assert_eq!(
+        set.try_insert_at(2, 0),
+        Err(AlreadyPresent {
+            element: 2,
+            index: 2
+        })
+    );
+
+    // A failed insertion leaves the set unchanged
+    assert_eq!(set.get_at(0), None);
+    assert_eq!(set.get_at(2), Some(&2));
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn try_insert_at_cap_panics() {
+    let mut set: PetitSet<u8, 4> = PetitSet::default();
+    let _ = set.try_insert_at(1, 4);
+}
+
+#[test]
+fn shrink() {
+    let mut set: PetitSet<u8, 8> = PetitSet::default();
+    set.insert_at(1, 3);
+    set.insert_at(2, 6);
+
+    let shrunk: PetitSet<u8, 2> = set.shrink().unwrap();
+    assert_eq!(shrunk.get_at(0), Some(&1));
+    assert_eq!(shrunk.get_at(1), Some(&2));
+
+    let failed: Result<PetitSet<u8, 1>, _> = set.shrink();
+    assert_eq!(failed, Err(CapacityError(2)));
+}
+
+#[test]
+fn from_fn() {
+    let set: PetitSet<usize, 4> = PetitSet::from_fn(|i| i * 10);
+    assert_eq!(set.len(), 4);
+    for index in 0..4 {
+        assert_eq!(set.get_at(index), Some(&(index * 10)));
+    }
+
+    // Duplicates are dropped, leaving empty slots at the end
+    let set: PetitSet<usize, 4> = PetitSet::from_fn(|i| i % 3);
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.get_at(2), Some(&2));
+    assert_eq!(set.get_at(3), None);
+}
+
+#[test]
+fn index_by_slot() {
+    let mut set: PetitSet<u8, 4> = PetitSet::default();
+    set.insert_at(7, 2);
+
+    assert_eq!(set[2], 7);
+    set[2] = 8;
+    assert_eq!(set[2], 8);
+}
+
+#[test]
+#[should_panic(expected = "slot 1 of PetitSet is empty")]
+fn index_empty_slot_panics() {
+    let set: PetitSet<u8, 4> = PetitSet::default();
+    let _ = set[1];
+}
+
+#[test]
+#[should_panic(expected = "index 4 out of bounds for PetitSet of capacity 4")]
+fn index_out_of_bounds_panics() {
+    let set: PetitSet<u8, 4> = PetitSet::default();
+    let _ = set[4];
+}
+
+#[test]
+fn set_algebra_into_works_on_stable() {
+    let set_a: PetitSet<u8, 3> = PetitSet::from_iter([1, 2, 3]);
+    let set_b: PetitSet<u8, 4> = PetitSet::from_iter([3, 4, 5]);
+
+    let mut symmetric_difference: PetitSet<u8, 4> = PetitSet::new();
+    set_a
+        .symmetric_difference_into(&set_b, &mut symmetric_difference)
+        .unwrap();
+    assert_eq!(
+        symmetric_difference,
+        PetitSet::<u8, 4>::from_iter([1, 2, 4, 5])
+    );
+
+    // Elements already in the output are kept
+    let mut intersection: PetitSet<u8, 2> = PetitSet::from_iter([9]);
+    set_a.intersection_into(&set_b, &mut intersection).unwrap();
+    assert_eq!(intersection, PetitSet::<u8, 2>::from_iter([9, 3]));
+
+    let mut difference: PetitSet<u8, 1> = PetitSet::new();
+    assert_eq!(
+        set_a.difference_into(&set_b, &mut difference),
+        Err(CapacityError(2))
+    );
+
+    let mut union: PetitSet<u8, 5> = PetitSet::new();
+    set_a.union_into(&set_b, &mut union).unwrap();
+    assert_eq!(union, PetitSet::<u8, 5>::from_iter([1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn array_round_trip() {
+    let raw = [None, Some(3), None, Some(1)];
+    let set: PetitSet<u8, 4> = PetitSet::from_raw_array_unchecked(raw);
+
+    assert_eq!(set.as_array(), [None, Some(&3), None, Some(&1)]);
+    assert_eq!(set.into_array(), raw);
+}
+
+#[test]
+fn predicate_helpers() {
+    let set: PetitSet<u8, 8> = PetitSet::from_iter([1, 2, 3, 4, 5]);
+
+    assert_eq!(set.count_where(|x| x % 2 == 1), 3);
+    assert!(set.any(|x| *x == 4));
+    assert!(!set.any(|x| *x > 5));
+    assert!(set.all(|x| *x > 0));
+    assert!(!set.all(|x| *x > 1));
+
+    let empty: PetitSet<u8, 8> = PetitSet::new();
+    assert_eq!(empty.count_where(|_| true), 0);
+    assert!(!empty.any(|_| true));
+    assert!(empty.all(|_| false));
+}
+
+#[test]
+fn try_from_array_of_any_length() {
+    let set = PetitSet::<u8, 3>::try_from([1, 2, 1, 3]).unwrap();
+    assert_eq!(set, PetitSet::<u8, 3>::from_iter([1, 2, 3]));
+
+    let failed = PetitSet::<u8, 2>::try_from([1, 2, 3, 4]);
+    assert_eq!(failed, Err(CapacityError((PetitSet::from_iter([1, 2]), 3))));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn try_from_vec() {
+    let set = PetitSet::<u8, 3>::try_from(vec![3, 3, 2]).unwrap();
+    assert_eq!(set.get_at(0), Some(&3));
+    assert_eq!(set.get_at(1), Some(&2));
+    assert_eq!(set.len(), 2);
+
+    assert!(PetitSet::<u8, 1>::try_from(vec![3, 2]).is_err());
+}
+
+#[test]
+fn mutate_removes_new_duplicates() {
+    let mut set: PetitSet<String, 4> = PetitSet::default();
+    set.insert_at("b".to_string(), 0);
+    set.insert_at("A".to_string(), 1);
+    set.insert_at("a".to_string(), 3);
+
+    set.mutate(|s| *s = s.to_lowercase());
+
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.get_at(0), Some(&"b".to_string()));
+    // The element in the lowest slot survives
+    assert_eq!(set.get_at(1), Some(&"a".to_string()));
+    assert_eq!(set.get_at(3), None);
+
+    // Freed slots are reused
+    set.insert("c".to_string());
+    assert_eq!(set.get_at(2), Some(&"c".to_string()));
+}
+
+#[test]
+fn into_set_after_partial_iteration() {
+    let set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    let mut iter = set.into_iter();
+    assert_eq!(iter.next(), Some(1));
+
+    let remaining = iter.into_set();
+    assert_eq!(remaining.len(), 2);
+    assert_eq!(remaining.get_at(0), None);
+    assert_eq!(remaining.get_at(1), Some(&2));
+    assert_eq!(remaining.get_at(2), Some(&3));
+}
+
+#[test]
+fn intersection_len() {
+    let set_a: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    let set_b: PetitSet<u8, 8> = PetitSet::from_iter([2, 3, 4, 5, 6]);
+    let empty: PetitSet<u8, 2> = PetitSet::new();
+
+    assert_eq!(set_a.intersection_len(&set_b), 2);
+    assert_eq!(set_b.intersection_len(&set_a), 2);
+    assert_eq!(set_a.intersection_len(&set_a), 3);
+    assert_eq!(set_a.intersection_len(&empty), 0);
+}
+
+#[test]
+fn similarity_metrics() {
+    let set_a: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3, 4]);
+    let set_b: PetitSet<u8, 4> = PetitSet::from_iter([3, 4, 5]);
+    let subset: PetitSet<u8, 2> = PetitSet::from_iter([1, 2]);
+    let disjoint: PetitSet<u8, 2> = PetitSet::from_iter([8, 9]);
+    let empty: PetitSet<u8, 2> = PetitSet::new();
+
+    assert_eq!(set_a.jaccard_similarity(&set_b), 2.0 / 5.0);
+    assert_eq!(set_a.jaccard_similarity(&set_a), 1.0);
+    assert_eq!(set_a.jaccard_similarity(&disjoint), 0.0);
+    assert_eq!(empty.jaccard_similarity(&empty), 1.0);
+    assert_eq!(set_a.jaccard_similarity(&empty), 0.0);
+
+    assert_eq!(set_a.overlap_coefficient(&set_b), 2.0 / 3.0);
+    assert_eq!(set_a.overlap_coefficient(&subset), 1.0);
+    assert_eq!(set_a.overlap_coefficient(&disjoint), 0.0);
+    assert_eq!(set_a.overlap_coefficient(&empty), 1.0);
+}
+
+#[test]
+fn remaining_capacity() {
+    let mut set: PetitSet<u8, 4> = PetitSet::new();
+    assert_eq!(set.remaining_capacity(), 4);
+    assert!(set.can_fit(4));
+
+    set.extend([1, 2, 3]);
+    assert_eq!(set.remaining_capacity(), 1);
+    assert!(set.can_fit(1));
+    assert!(!set.can_fit(2));
+
+    set.insert(4);
+    assert_eq!(set.remaining_capacity(), 0);
+    assert!(set.can_fit(0));
+}
+
+#[test]
+fn truncate() {
+    let mut set: PetitSet<u8, 6> = PetitSet::from_iter([1, 2, 3, 4, 5]);
+    set.remove(&2);
+
+    set.truncate(10);
+    assert_eq!(set.len(), 4);
+
+    set.truncate(2);
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.get_at(0), Some(&1));
+    assert_eq!(set.get_at(2), Some(&3));
+    assert!(!set.contains(&4));
+    assert!(!set.contains(&5));
+
+    set.truncate(0);
+    assert!(set.is_empty());
+}
+
+#[test]
+fn intersection_and_difference_operators() {
+    let set_a: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    let set_b: PetitSet<u8, 8> = PetitSet::from_iter([2, 3, 4, 5]);
+
+    let intersection: PetitSet<u8, 4> = &set_a & &set_b;
+    assert_eq!(intersection, PetitSet::<u8, 2>::from_iter([2, 3]));
+
+    let difference: PetitSet<u8, 4> = &set_a - &set_b;
+    assert_eq!(difference, PetitSet::<u8, 1>::from_iter([1]));
+
+    let reverse_difference: PetitSet<u8, 8> = &set_b - &set_a;
+    assert_eq!(reverse_difference, PetitSet::<u8, 2>::from_iter([4, 5]));
+}
+
+#[test]
+fn in_place_set_operations() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3, 4]);
+    set.difference_update(&PetitSet::<u8, 2>::from_iter([2, 9]));
+    assert_eq!(set, PetitSet::<u8, 3>::from_iter([1, 3, 4]));
+    // Remaining elements stay in place
+    assert_eq!(set.get_at(2), Some(&3));
+
+    set.intersection_update(&PetitSet::<u8, 3>::from_iter([3, 4, 5]));
+    assert_eq!(set, PetitSet::<u8, 2>::from_iter([3, 4]));
+
+    set.symmetric_difference_update(PetitSet::<u8, 3>::from_iter([4, 5, 6]))
+        .unwrap();
+    assert_eq!(set, PetitSet::<u8, 3>::from_iter([3, 5, 6]));
+
+    let mut full: PetitSet<u8, 2> = PetitSet::from_iter([1, 2]);
+    assert_eq!(
+        full.symmetric_difference_update(PetitSet::<u8, 2>::from_iter([3, 4])),
+        Err(CapacityError(3))
+    );
+}
+
+#[test]
+fn petitset_macro() {
+    let set = petitset![3, 1, 2];
+    assert_eq!(set.capacity(), 3);
+    assert!(set.is_full());
+    assert_eq!(set.get_at(0), Some(&3));
+
+    let deduped = petitset!["a", "b", "a",];
+    assert_eq!(deduped.capacity(), 3);
+    assert_eq!(deduped.len(), 2);
+
+    let empty: PetitSet<u8, 0> = petitset![];
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn get_at_pair_mut() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+
+    let (a, b) = set.get_at_pair_mut(2, 0).unwrap();
+    assert_eq!((*a, *b), (3, 1));
+    core::mem::swap(a, b);
+    assert_eq!(set.get_at(0), Some(&3));
+    assert_eq!(set.get_at(2), Some(&1));
+
+    assert!(set.get_at_pair_mut(1, 1).is_none());
+    assert!(set.get_at_pair_mut(0, 3).is_none());
+    assert!(set.get_at_pair_mut(0, 4).is_none());
+}
+
+#[test]
+fn set_iter_default_is_empty() {
+    let mut iter: PetitSetIter<u8, 4> = PetitSetIter::default();
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert!(iter.into_set().is_empty());
+}
+
+#[test]
+fn with_elements() {
+    let set: PetitSet<u8, 4> = PetitSet::with_elements([3, 1, 3]);
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.get_at(0), Some(&3));
+    assert_eq!(set.get_at(1), Some(&1));
+
+    let full: PetitSet<u8, 3> = PetitSet::with_elements([1, 2, 3]);
+    assert!(full.is_full());
+}
+
+#[test]
+fn indices() {
+    let mut set: PetitSet<u8, 5> = PetitSet::from_iter([1, 2, 3, 4]);
+    set.remove(&2);
+
+    let indices: Vec<usize> = set.indices().collect();
+    assert_eq!(indices, vec![0, 2, 3]);
+
+    for index in set.indices() {
+        let element = set.get_at(index).unwrap();
+        assert_eq!(set.find(element), Some(index));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn chunks() {
+    let mut set: PetitSet<u8, 8> = PetitSet::from_iter(1..=8);
+    set.remove(&3);
+
+    let chunks: Vec<Vec<&u8>> = set.chunks(3).collect();
+    assert_eq!(chunks, vec![vec![&1, &2, &4], vec![&5, &6, &7], vec![&8]]);
+
+    let empty: PetitSet<u8, 8> = PetitSet::default();
+    assert_eq!(empty.chunks(3).count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn chunks_of_zero() {
+    let set: PetitSet<u8, 4> = PetitSet::from_iter(1..=4);
+    let _ = set.chunks(0);
+}
+
+#[test]
+fn clone_from_reuses_elements() {
+    #[derive(Debug)]
+    struct Recycled {
+        id: u8,
+        reused: bool,
+    }
+
+    impl Recycled {
+        fn new(id: u8) -> Self {
+            Recycled { id, reused: false }
+        }
+    }
+
+    impl Clone for Recycled {
+        fn clone(&self) -> Self {
+            Recycled::new(self.id)
+        }
+
+        fn clone_from(&mut self, source: &Self) {
+            self.id = source.id;
+            self.reused = true;
+        }
+    }
+
+    impl PartialEq for Recycled {
+        fn eq(&self, other: &Self) -> bool {
+            self.id == other.id
+        }
+    }
+
+    impl Eq for Recycled {}
+
+    let source: PetitSet<Recycled, 4> = PetitSet::from_iter([Recycled::new(4), Recycled::new(5)]);
+    let mut set: PetitSet<Recycled, 4> = PetitSet::from_iter([1, 2, 3].map(Recycled::new));
+
+    set.clone_from(&source);
+    assert_eq!(set, source);
+    assert_eq!(set.len(), 2);
+    assert!(set[0].reused);
+    assert!(set[1].reused);
+    assert_eq!(set.get_at(2), None);
+
+    let mut empty: PetitSet<Recycled, 4> = PetitSet::default();
+    empty.clone_from(&source);
+    assert_eq!(empty, source);
+    assert!(!empty[0].reused);
+}
+
+#[test]
+fn occupied_slots_mut() {
+    let mut set: PetitSet<u8, 5> = PetitSet::from_iter([1, 2, 3, 4]);
+    set.remove(&2);
+
+    let offsets = [10, 20, 30, 40, 50];
+    for (index, element) in set.occupied_slots_mut() {
+        *element += offsets[index];
+    }
+
+    let slots: Vec<(usize, &u8)> = set.iter_indexed().collect();
+    assert_eq!(slots, vec![(0, &11), (2, &33), (3, &44)]);
+}
+
+#[test]
+fn from_iter_counting() {
+    let (set, duplicates) = PetitSet::<u8, 4>::from_iter_counting([3, 1, 3, 3, 2]);
+    assert_eq!(set, PetitSet::<u8, 4>::from_iter([3, 1, 2]));
+    assert_eq!(duplicates, 2);
+
+    let (empty, duplicates) = PetitSet::<u8, 4>::from_iter_counting([]);
+    assert!(empty.is_empty());
+    assert_eq!(duplicates, 0);
+}
+
+#[test]
+fn try_from_iter_counting_overflow() {
+    let result = PetitSet::<u8, 2>::try_from_iter_counting([1, 1, 2, 3, 4]);
+    assert_eq!(
+        result,
+        Err(CapacityError((PetitSet::<u8, 2>::from_iter([1, 2]), 3)))
+    );
+}
+
+#[test]
+#[should_panic]
+fn from_iter_counting_overflow() {
+    let _ = PetitSet::<u8, 2>::from_iter_counting([1, 2, 3]);
+}
+
+#[test]
+fn swap_remove() {
+    let mut set: PetitSet<u8, 5> = PetitSet::from_iter([1, 2, 3, 4]);
+
+    assert_eq!(set.swap_remove(&2), Some(2));
+    let slots: Vec<(usize, &u8)> = set.iter_indexed().collect();
+    assert_eq!(slots, vec![(0, &1), (1, &4), (2, &3)]);
+
+    assert_eq!(set.swap_remove(&2), None);
+    assert_eq!(set.len(), 3);
+
+    // Removing the last element moves nothing
+    assert_eq!(set.swap_remove(&3), Some(3));
+    let slots: Vec<(usize, &u8)> = set.iter_indexed().collect();
+    assert_eq!(slots, vec![(0, &1), (1, &4)]);
+
+    // The set stays packed, so new elements fill the end
+    set.insert(5);
+    assert_eq!(set.find(&5), Some(2));
+}
+
+#[test]
+fn swap_remove_at() {
+    let mut set: PetitSet<u8, 5> = PetitSet::from_iter([1, 2, 3]);
+
+    assert_eq!(set.swap_remove_at(0), Some(1));
+    assert_eq!(set.get_at(0), Some(&3));
+    assert_eq!(set.get_at(2), None);
+
+    assert_eq!(set.swap_remove_at(4), None);
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "index 5 out of bounds for PetitSet of capacity 5")]
+fn swap_remove_at_out_of_bounds() {
+    let mut set: PetitSet<u8, 5> = PetitSet::from_iter([1, 2, 3]);
+    set.swap_remove_at(5);
+}
+
+#[test]
+fn capacity_error_accessors() {
+    let error = PetitSet::<u8, 2>::try_from_iter([1, 2, 3]).unwrap_err();
+    assert_eq!(error.partial(), &PetitSet::<u8, 2>::from_iter([1, 2]));
+    assert_eq!(error.rejected(), &3);
+
+    let (partial, rejected) = error.into_parts();
+    assert_eq!(partial.len(), 2);
+    assert_eq!(rejected, 3);
+
+    let mut set: PetitSet<u8, 1> = PetitSet::from_iter([1]);
+    let error = set.try_insert(2).unwrap_err();
+    assert_eq!(error.element(), &2);
+    assert_eq!(error.map(u32::from), CapacityError(2u32));
+    assert_eq!(set.try_insert(3).unwrap_err().into_inner(), 3);
+}
+
+#[test]
+fn extract_if_debug() {
+    let mut set: PetitSet<u8, 4> = PetitSet::from_iter([1, 2, 3]);
+    let mut extract = set.extract_if(|element| element % 2 == 1);
+    assert_eq!(extract.next(), Some(1));
+
+    assert_eq!(
+        format!("{extract:?}"),
+        "PetitSetExtractIf { set: {2, 3}, cursor: 1, .. }"
+    );
 }
@@ -0,0 +1,20 @@
+#![cfg(feature = "set_algebra")]
+
+use petitset::PetitSet;
+
+#[test]
+fn union_and_symmetric_difference_operators() {
+    let set_a: PetitSet<u8, 3> = PetitSet::from_iter([1, 2, 3]);
+    let set_b: PetitSet<u8, 2> = PetitSet::from_iter([3, 4]);
+
+    let union = &set_a | &set_b;
+    assert_eq!(union.capacity(), 5);
+    assert_eq!(union, PetitSet::<u8, 4>::from_iter([1, 2, 3, 4]));
+
+    let symmetric_difference = &set_a ^ &set_b;
+    assert_eq!(symmetric_difference.capacity(), 5);
+    assert_eq!(
+        symmetric_difference,
+        PetitSet::<u8, 3>::from_iter([1, 2, 4])
+    );
+}